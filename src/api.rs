@@ -1,130 +1,435 @@
 // izone/src/api.rs
 
-use reqwest::blocking::Client;
-use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
-use serde_json::Value;
-
-// Import the necessary constants and the global VERBOSE flag
-// Note: VERBOSE is a static mut, so we access it via constants::VERBOSE
-use crate::constants::{IZONE_IP, QUERY_URL_SUFFIX, COMMAND_URL_SUFFIX, VERBOSE};
-use colored::Colorize; // Re-added Colorize for error messages in this module
-use std::process::exit; // Re-added exit for immediate termination on API errors
-
-/// Makes a POST request to the iZone API query endpoint.
-///
-/// Constructs the full URL using IZONE_IP and QUERY_URL_SUFFIX.
-/// Includes JSON payload and sets Content-Type header.
-/// Prints verbose output if the global VERBOSE flag is true.
-/// Returns a `Result` to allow caller to handle errors gracefully.
-pub fn make_query_request(client: &Client, payload: Value) -> Result<Value, String> {
-    // Construct the full QUERY_URL using IZONE_IP and QUERY_URL_SUFFIX
-    let query_url = format!("{}{}", &**IZONE_IP, QUERY_URL_SUFFIX);
-
-    // Create headers
-    let mut headers = HeaderMap::new();
-    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-
-    // Make the request
-    let response = client
-        .post(&query_url)
-        .headers(headers)
-        .json(&payload)
-        .send();
-
-    match response {
-        Ok(res) => {
-            let status = res.status();
-            let json_body: Value = res.json().map_err(|e| {
-                format!(
-                    "Unexpected response from iZone controller at {}.\n\
-                    Ensure your configuration has the correct iZone IP and the controller is reachable in your network.\n\
-                    Error details: {}",
-                    &**IZONE_IP, e
-                )
-            })?;
-
-            // Access the global VERBOSE flag safely within the unsafe block
-            if unsafe { VERBOSE } {
-                println!("Request URL: {}", query_url);
-                println!("Request Payload: {}", payload);
-                println!("Response Status: {}", status);
-                println!("Response Body: {}", serde_json::to_string_pretty(&json_body).unwrap_or_default());
-            }
+//! Request builders for the iZone V2 HTTP API.
+//!
+//! Every payload sent to `/iZoneRequestV2` or `/iZoneCommandV2` is built here,
+//! so the transport in `client.rs` only has to deal with sending. Response
+//! checking lives here too, so every transport reports the same errors.
+
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+use crate::error::{IzoneError, Result};
+use crate::models::{GasHeat, ZoneMode};
+
+/// `iZoneV2Request` type for system information (`SystemV2`).
+pub const REQUEST_SYSTEM: u8 = 1;
+/// `iZoneV2Request` type for zone information (`ZonesV2`).
+pub const REQUEST_ZONE: u8 = 2;
+/// `iZoneV2Request` type for favourite / schedule information (`SchedulesV2`).
+pub const REQUEST_SCHEDULE: u8 = 3;
+/// `iZoneV2Request` type for the AC unit fault history (`AcUnitFaultHistV2`).
+pub const REQUEST_AC_FAULTS: u8 = 4;
+/// `iZoneV2Request` type for the Temperzone unit status (`TemperzoneInfoV2`).
+pub const REQUEST_TEMPERZONE: u8 = 5;
+/// `iZoneV2Request` type for the device / firmware list (`Fmw`).
+pub const REQUEST_FIRMWARE: u8 = 6;
+
+/// Builds an `iZoneV2Request` query payload.
+pub fn query(request_type: u8, no: u8) -> Value {
+    json!({ "iZoneV2Request": { "Type": request_type, "No": no, "No1": 0 } })
+}
+
+/// Query for the overall system status.
+pub fn system_query() -> Value {
+    query(REQUEST_SYSTEM, 0)
+}
+
+/// Query for a single zone (0-based index).
+pub fn zone_query(index: u8) -> Value {
+    query(REQUEST_ZONE, index)
+}
+
+/// Query for a single favourite / schedule (0-based index).
+pub fn schedule_query(index: u8) -> Value {
+    query(REQUEST_SCHEDULE, index)
+}
+
+/// Query for the AC unit fault history (last 11 faults).
+pub fn fault_history_query() -> Value {
+    query(REQUEST_AC_FAULTS, 0)
+}
+
+/// Query for the Temperzone outdoor unit status.
+pub fn temperzone_query() -> Value {
+    query(REQUEST_TEMPERZONE, 0)
+}
+
+/// Query for the connected devices and their firmware versions.
+pub fn firmware_query() -> Value {
+    query(REQUEST_FIRMWARE, 0)
+}
+
+/// `SysOn` command: turn the AC unit on or off.
+pub fn sys_on_command(on: bool) -> Value {
+    json!({ "SysOn": u8::from(on) })
+}
+
+/// `SysMode` command, `mode` is a `SysMode_e` value.
+pub fn sys_mode_command(mode: u8) -> Value {
+    json!({ "SysMode": mode })
+}
+
+/// `SysFan` command, `fan` is a `SysFan_e` value.
+pub fn sys_fan_command(fan: u8) -> Value {
+    json!({ "SysFan": fan })
+}
+
+/// `SysSetpoint` command, setpoint is x100 (e.g. 2250 = 22.5°C).
+pub fn sys_setpoint_command(setpoint: u32) -> Value {
+    json!({ "SysSetpoint": setpoint })
+}
+
+/// `ZoneMode` command for a single zone.
+pub fn zone_mode_command(index: u8, mode: ZoneMode) -> Value {
+    json!({ "ZoneMode": { "Index": index, "Mode": mode as u8 } })
+}
+
+/// `ZoneSetpoint` command, setpoint is x100 (e.g. 2250 = 22.5°C).
+pub fn zone_setpoint_command(index: u8, setpoint: u32) -> Value {
+    json!({ "ZoneSetpoint": { "Index": index, "Setpoint": setpoint } })
+}
+
+/// `ZoneSetting` command: sensor (`RoomSensorType_t`), zone type (`ZoneType_e`)
+/// and, for constant zones, the constant number.
+pub fn zone_setting_command(index: u8, sensor: u8, zone_type: u8, const_no: u8) -> Value {
+    json!({ "ZoneSetting": { "Index": index, "Sensor": sensor, "Zone": zone_type, "ConstNo": const_no } })
+}
+
+/// `RfPair` command: puts the controller into pairing mode for wireless
+/// (CRFS) sensors. Not zone specific, the next sensor to press pair is taken.
+pub fn rf_pair_command() -> Value {
+    json!({ "RfPair": 1 })
+}
+
+/// `Crfs2SensorPair` command: pairs a CRFS2 sensor to a zone. `tx_interval`
+/// is a `Crfs2TxInterval_e` (0 = not set, 1 = 2s ... 8 = 256s).
+pub fn crfs2_sensor_pair_command(index: u8, tx_interval: u8) -> Value {
+    json!({ "Crfs2SensorPair": { "Index": index, "TxInterval": tx_interval } })
+}
+
+/// `ChangeRfCh` command, channel 1-8.
+pub fn change_rf_channel_command(channel: u8) -> Value {
+    json!({ "ChangeRfCh": channel })
+}
+
+/// `RASSet` command: the unit's control source, a `ReturnAirSensor_e`
+/// (1 = RAS, 2 = master zone, 3 = zones).
+pub fn ras_set_command(ras: u8) -> Value {
+    json!({ "RASSet": ras })
+}
+
+/// `MasterZone` command: the zone index that controls the unit when the
+/// control source is the master zone.
+pub fn master_zone_command(index: u8) -> Value {
+    json!({ "MasterZone": index })
+}
+
+/// Longest tag line the controller stores (32 bytes including the terminator).
+pub const SYS_TAG_MAX_LEN: usize = 31;
+
+/// Longest configuration password the controller stores (16 bytes including
+/// the terminator).
+pub const PASSWORD_MAX_LEN: usize = 15;
 
-            if status.is_success() {
-                Ok(json_body)
-            } else {
-                Err(format!("API query failed with status: {} and body: {}", status, json_body))
+/// `SysTag1` / `SysTag2` command: sets tag line 1 or 2 (`line` is 1 or 2).
+pub fn sys_tag_command(line: u8, text: &str) -> Value {
+    let key = if line == 1 { "SysTag1" } else { "SysTag2" };
+    json!({ key: text })
+}
+
+/// `ChangePass` command: sets the system configuration password.
+pub fn change_pass_command(password: &str) -> Value {
+    json!({ "ChangePass": password })
+}
+
+/// `LockSystem` command: `lock` starts the lock timer, which locks the system
+/// after `days`; unlocking needs the `code` (up to 6 digits).
+pub fn lock_system_command(lock: bool, code: &str, days: u16) -> Value {
+    json!({ "LockSystem": { "Lock": lock as u8, "LockCode": code, "LockDays": days } })
+}
+
+/// Whether a command payload is `LockSystem`, the only command a locked
+/// system accepts.
+pub fn is_lock_command(payload: &Value) -> bool {
+    payload.get("LockSystem").is_some()
+}
+
+/// `NoOfZones` command: the number of zones installed (1-14).
+pub fn no_of_zones_command(zones: u8) -> Value {
+    json!({ "NoOfZones": zones })
+}
+
+/// `NoOfConstants` command: the number of constants, at most `NoOfZones`.
+pub fn no_of_constants_command(constants: u8) -> Value {
+    json!({ "NoOfConstants": constants })
+}
+
+/// Linkage command: enables or disables one of the `iZone*` / `Ext*` control
+/// flags, `key` as returned by `helpers::get_linkage_key`.
+pub fn linkage_command(key: &str, enable: bool) -> Value {
+    json!({ key: enable as u8 })
+}
+
+/// Panel setting command: `key` as returned by `helpers::get_panel_key`
+/// (`AutoOff`, `RoomTempDisp`, `SetWiredLeds`, `HideInduct` or `ShowActTemps`).
+pub fn panel_command(key: &str, enable: bool) -> Value {
+    json!({ key: enable as u8 })
+}
+
+/// `iSaveOn` command: turns iSave on or off (needs `iSaveEnable`).
+pub fn isave_on_command(on: bool) -> Value {
+    json!({ "iSaveOn": on as u8 })
+}
+
+/// `FanAutoEn` command: enables the fan auto function.
+pub fn fan_auto_enable_command(enable: bool) -> Value {
+    json!({ "FanAutoEn": enable as u8 })
+}
+
+/// `FanAutoType` command, `fan_type` is a `FanAutoType_e` value.
+pub fn fan_auto_type_command(fan_type: u8) -> Value {
+    json!({ "FanAutoType": fan_type })
+}
+
+/// `FanCapacity` command: the fan airflow rating used by fan auto.
+pub fn fan_capacity_command(capacity: u16) -> Value {
+    json!({ "FanCapacity": capacity })
+}
+
+/// `FanUnitCapacity` command: the AC unit capacity in kW used by fan auto.
+pub fn fan_unit_capacity_command(kw: u16) -> Value {
+    json!({ "FanUnitCapacity": kw })
+}
+
+/// `TemperzoneSettingsSetpoints` command, both x100: heat 3000-4000, cool 500-1500.
+pub fn temperzone_setpoints_command(heat: u32, cool: u32) -> Value {
+    json!({ "TemperzoneSettingsSetpoints": { "HeatSetpoint": heat, "CoolSetpoint": cool } })
+}
+
+/// `TemperzoneSettingsUnit` command: `TemperzoneFanType_t` and `TemperzoneModeType_t`.
+pub fn temperzone_unit_command(fan_type: u8, mode_type: u8) -> Value {
+    json!({ "TemperzoneSettingsUnit": { "FanType": fan_type, "ModeType": mode_type } })
+}
+
+/// `TemperzoneQuietMode` command: outdoor fan quiet mode.
+pub fn temperzone_quiet_command(quiet: bool) -> Value {
+    json!({ "TemperzoneQuietMode": quiet as u8 })
+}
+
+/// `GasHeatSettings` command for the universal (gas heat) controller. All
+/// seven fields are required; `StageOffset` is x10 °C.
+pub fn gas_heat_settings_command(settings: &GasHeat) -> Value {
+    json!({ "GasHeatSettings": {
+        "Type": settings.gas_type,
+        "MinRunTime": settings.min_run_time,
+        "AnticycleTime": settings.anticycle_time,
+        "StageOffset": settings.stage_offset,
+        "StageDelay": settings.stage_delay,
+        "CycleFanCool": settings.cycle_fan_cool,
+        "CycleFanHeat": settings.cycle_fan_heat,
+    } })
+}
+
+/// Commands that set an absolute value, so sending them twice has the same
+/// effect as sending them once. Only these are retried after a failure.
+const IDEMPOTENT_COMMANDS: &[&str] = &[
+    "SysOn", "SysMode", "SysFan", "SysSetpoint",
+    "ZoneMode", "ZoneSetpoint", "ZoneName", "ZoneAirflow", "ZoneArea", "ZoneBypass",
+    "BalanceMax", "BalanceMin", "DamperSkip", "SensorCalib", "ZoneSetting",
+    "SchedName", "SchedEnable", "SchedAcMode", "SchedAcFan",
+    "EconomyLock", "EconomyMin", "EconomyMax", "FilterWarn", "DamperTime", "AutoModeDeadB",
+    "AirflowLock", "AirflowMinLock", "StaticP", "OpenDampersWhenOff", "ScroogeMode",
+    "ReverseDampers", "CnstCtrlAreaEn", "CnstCtrlArea",
+    "ChangeRfCh", "RASSet", "MasterZone", "SysTag1", "SysTag2", "ChangePass", "LockSystem",
+    "NoOfZones", "NoOfConstants", "FanAutoEn", "FanAutoType", "FanCapacity", "FanUnitCapacity",
+    "iSaveOn", "AutoOff", "RoomTempDisp", "SetWiredLeds", "HideInduct", "ShowActTemps",
+    "iZoneOnOff", "iZoneMode", "iZoneFan", "iZoneSetpoint", "ExtOnOff", "ExtMode", "ExtFan", "ExtSetpoint",
+    "TemperzoneSettingsSetpoints", "TemperzoneSettingsUnit", "TemperzoneQuietMode", "GasHeatSettings",
+    "CoolbreezeFanSpeed", "CoolbreezeRhSetpoint", "CoolbreezeFanMax", "CoolbreezeFanMaxAuto",
+    "CoolbreezeExhMax", "CoolbreezeExhEn", "CoolbreezeInverter", "CoolbreezeResumeLast",
+    "VentilationRfSetpoint", "VentilationVocsSetpoint", "VentilationEco2Setpoint",
+];
+
+/// Whether a command payload is safe to retry (see `IDEMPOTENT_COMMANDS`).
+pub fn is_idempotent(payload: &Value) -> bool {
+    payload
+        .as_object()
+        .is_some_and(|obj| !obj.is_empty() && obj.keys().all(|k| IDEMPOTENT_COMMANDS.contains(&k.as_str())))
+}
+
+/// Delay before retry number `attempt + 1`: `backoff`, doubled for each further retry.
+pub fn retry_delay(backoff: Duration, attempt: u32) -> Duration {
+    backoff.saturating_mul(2u32.saturating_pow(attempt))
+}
+
+/// Checks the HTTP status of a response, keeping the (masked) body for the error.
+pub fn check_status(url: &str, status: u16, body: &str) -> Result<()> {
+    if (200..300).contains(&status) {
+        Ok(())
+    } else {
+        Err(IzoneError::HttpStatus { url: url.to_string(), status, body: mask_secrets(body) })
+    }
+}
+
+/// JSON keys whose string values are secrets: the system configuration
+/// password in `SystemV2` and the `ChangePass` command.
+const SECRET_KEYS: &[&str] = &["Pass", "ChangePass"];
+
+/// Shown in place of a secret value.
+pub const MASK: &str = "****";
+
+/// Replaces the string values of `SECRET_KEYS` in JSON text with `MASK`, so
+/// request and response bodies can be logged or kept in errors.
+pub fn mask_secrets(text: &str) -> String {
+    let mut masked = String::with_capacity(text.len());
+    let mut rest = text;
+    'scan: while let Some(start) = rest.find('"') {
+        for key in SECRET_KEYS {
+            let quoted = format!("\"{}\"", key);
+            if let Some(after_key) = rest[start..].strip_prefix(quoted.as_str()) {
+                let value = after_key.trim_start();
+                if let Some(value) = value.strip_prefix(':').map(str::trim_start).and_then(|v| v.strip_prefix('"')) {
+                    let end = string_end(value);
+                    masked.push_str(&rest[..start]);
+                    masked.push_str(&quoted);
+                    masked.push_str(":\"");
+                    masked.push_str(MASK);
+                    masked.push('"');
+                    rest = &value[(end + 1).min(value.len())..];
+                    continue 'scan;
+                }
             }
         }
-        Err(e) => Err(format!(
-            "Failed to connect to iZone controller at {}.\n\
-            Ensure the IP address is correct and the controller is reachable in your network.\n\
-            Error details: {}",
-            &**IZONE_IP, e
-        )),
+        masked.push_str(&rest[..=start]);
+        rest = &rest[start + 1..];
+        // Skip the rest of this string so keys are only matched at a string start
+        let end = string_end(rest);
+        let end = (end + 1).min(rest.len());
+        masked.push_str(&rest[..end]);
+        rest = &rest[end..];
     }
+    masked.push_str(rest);
+    masked
 }
 
-/// Makes a POST request to the iZone API command endpoint.
-///
-/// Constructs the full URL using IZONE_IP and COMMAND_URL_SUFFIX.
-/// Includes JSON payload and sets Content-Type header.
-/// Prints verbose output if the global VERBOSE flag is true.
-/// Returns `Result<(), String>` as command responses are often non-JSON or empty.
-pub fn make_command_request(client: &Client, payload: Value) -> Result<(), String> { // Changed return type
-    // Construct the full COMMAND_URL using IZONE_IP and COMMAND_URL_SUFFIX
-    let command_url = format!("{}{}", &**IZONE_IP, COMMAND_URL_SUFFIX);
-
-    // Create headers
-    let mut headers = HeaderMap::new();
-    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-
-    // Make the request
-    let response = client
-        .post(&command_url)
-        .headers(headers)
-        .json(&payload)
-        .send();
-
-    match response {
-        Ok(res) => {
-            let status = res.status();
-            // Changed to read response as text, as commands may not return JSON
-            let res_text = res.text().map_err(|e| {
-                format!(
-                    "Unexpected response from iZone controller at {}.\n\
-                    Ensure your configuration has the correct iZone IP and the controller is reachable in your network.\n\
-                    Error details: {}",
-                    &**IZONE_IP, e
-                )
-            })?;
-
-            // Access the global VERBOSE flag safely within the unsafe block
-            if unsafe { VERBOSE } {
-                println!("Request URL: {}", command_url);
-                println!("Request Payload: {}", payload);
-                println!("Response Status: {}", status);
-                println!("Response Body (Text): {}", res_text); // Print as text
-            }
+/// Byte offset of the closing quote of a JSON string whose opening quote has
+/// already been consumed, or the text length when it is unterminated.
+fn string_end(text: &str) -> usize {
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return i,
+            _ => escaped = false,
+        }
+    }
+    text.len()
+}
 
-            // Check for common error indicators in the text response
-            if res_text.contains("error") || res_text.contains("Error") {
-                eprintln!("{}", format!("API Error: {}", res_text).red());
-                exit(1); // Exit directly on API error for commands
-            }
+/// Parses a query response body, `what` names the response in error messages.
+pub fn parse_response<T: DeserializeOwned>(url: &str, what: &str, body: &str) -> Result<T> {
+    serde_json::from_str(body).map_err(|e| IzoneError::Parse {
+        url: url.to_string(),
+        what: what.to_string(),
+        details: e.to_string(),
+        body: mask_secrets(body),
+    })
+}
 
-            if status.is_success() {
-                Ok(()) // Return Ok(()) on success
-            } else {
-                Err(format!("API command failed with status: {} and body: {}", status, res_text))
-            }
+/// Command responses are usually plain text; the controller reports failures
+/// by including "error" in it.
+pub fn check_command_response(url: &str, body: &str) -> Result<()> {
+    if body.contains("error") || body.contains("Error") {
+        Err(IzoneError::Controller { url: url.to_string(), message: mask_secrets(body.trim()) })
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn absolute_settings_are_idempotent() {
+        for payload in [
+            sys_on_command(true),
+            sys_fan_command(4),
+            zone_mode_command(3, ZoneMode::Auto),
+            zone_setpoint_command(0, 2200),
+            master_zone_command(2),
+            change_pass_command("1234"),
+            lock_system_command(true, "2705", 3),
+            gas_heat_settings_command(&GasHeat::default()),
+        ] {
+            assert!(is_idempotent(&payload), "{}", payload);
         }
-        Err(e) => Err(format!(
-            "Failed to connect to iZone controller at {}.\n\
-            Ensure the IP address is correct and the controller is reachable in your network.\n\
-            Error details: {}",
-            &**IZONE_IP, e
-        )),
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn other_payloads_are_not_idempotent() {
+        for payload in [
+            rf_pair_command(),
+            crfs2_sensor_pair_command(1, 3),
+            system_query(),
+            json!({ "OutOfViewRAS": 1 }),
+            // Every key has to be idempotent, not just one of them
+            json!({ "SysOn": 1, "RfPair": 0 }),
+            json!({}),
+            json!("SysOn"),
+        ] {
+            assert!(!is_idempotent(&payload), "{}", payload);
+        }
+    }
+
+    #[test]
+    fn retry_delay_doubles_the_backoff() {
+        let backoff = Duration::from_millis(250);
+        assert_eq!(retry_delay(backoff, 0), Duration::from_millis(250));
+        assert_eq!(retry_delay(backoff, 1), Duration::from_millis(500));
+        assert_eq!(retry_delay(backoff, 2), Duration::from_millis(1000));
+        assert_eq!(retry_delay(Duration::ZERO, 5), Duration::ZERO);
+    }
+
+    #[test]
+    fn retry_delay_saturates_instead_of_overflowing() {
+        assert_eq!(retry_delay(Duration::MAX, 1), Duration::MAX);
+        assert_eq!(retry_delay(Duration::from_secs(1), 40), Duration::from_secs(u64::from(u32::MAX)));
+    }
+
+    #[test]
+    fn mask_secrets_masks_pass_and_change_pass() {
+        assert_eq!(mask_secrets(r#"{"Pass":"1234","SysOn":1}"#), r#"{"Pass":"****","SysOn":1}"#);
+        assert_eq!(mask_secrets(r#"{"ChangePass":"new-pass"}"#), r#"{"ChangePass":"****"}"#);
+        // Keys that only start with a secret key's name are left alone
+        assert_eq!(mask_secrets(r#"{"Passive":"yes","ChangePassword":"x"}"#), r#"{"Passive":"yes","ChangePassword":"x"}"#);
+    }
+
+    #[test]
+    fn mask_secrets_skips_escaped_quotes_inside_values() {
+        assert_eq!(mask_secrets(r#"{"Pass":"se\"cr\\et","Tag1":"Home"}"#), r#"{"Pass":"****","Tag1":"Home"}"#);
+        // A secret key's name inside another string value is not a key
+        assert_eq!(mask_secrets(r#"{"Tag1":"\"Pass\":\"x\"","Pass":"y"}"#), r#"{"Tag1":"\"Pass\":\"x\"","Pass":"****"}"#);
+    }
+
+    #[test]
+    fn mask_secrets_allows_whitespace_around_the_colon() {
+        assert_eq!(mask_secrets("{\"Pass\" :\n  \"1234\", \"SysOn\": 1}"), r#"{"Pass":"****", "SysOn": 1}"#);
+    }
+
+    #[test]
+    fn mask_secrets_leaves_non_string_values_as_they_are() {
+        let body = r#"{"Pass":null,"ChangePass":{"x":1},"SysOn":1}"#;
+        assert_eq!(mask_secrets(body), body);
+    }
+
+    #[test]
+    fn mask_secrets_keeps_bodies_without_secrets() {
+        for body in [r#"{"SystemV2":{"SysOn":1,"Tag1":"Home"}}"#, "{OK}", "", "\"unterminated"] {
+            assert_eq!(mask_secrets(body), body);
+        }
+    }
+}
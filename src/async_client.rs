@@ -0,0 +1,199 @@
+// izone/src/async_client.rs
+
+use std::time::{Duration, Instant};
+
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::api;
+use crate::client::{json_headers, log_response, log_retry, ClientOptions};
+use crate::constants::{COMMAND_URL_SUFFIX, QUERY_URL_SUFFIX};
+use crate::error::{IzoneError, Result};
+use crate::models::{
+    AcFault, AcUnitFaultHistResponse, Firmware, FirmwareListResponse, SchedulesResponseWrapper,
+    SchedulesV2, SystemV2, SystemV2Response, TemperzoneInfoResponse, TemperzoneInfoV2, ZoneMode,
+    ZonesV2, ZonesV2Response,
+};
+
+/// Async client for a single iZone bridge, for use on a tokio runtime.
+///
+/// Mirrors [`IzoneClient`](crate::IzoneClient) method for method; both share
+/// the request builders in [`api`](crate::api) and the models parsing.
+#[derive(Debug, Clone)]
+pub struct AsyncIzoneClient {
+    http: Client,
+    base_url: String,
+    retries: u32,
+    retry_backoff: Duration,
+    check_lock: bool,
+}
+
+impl AsyncIzoneClient {
+    /// Creates a client for the bridge at `base_url` (e.g. `http://192.168.1.130`).
+    pub fn new(base_url: impl Into<String>) -> Result<Self> {
+        Self::with_options(base_url, ClientOptions::default())
+    }
+
+    /// Creates a client for the bridge at `base_url` using the given options.
+    pub fn with_options(base_url: impl Into<String>, options: ClientOptions) -> Result<Self> {
+        let mut builder = Client::builder();
+        if let Some(timeout) = options.timeout {
+            builder = builder.timeout(timeout);
+        }
+        let base_url = base_url.into().trim_end_matches('/').to_string();
+        let http = builder
+            .build()
+            .map_err(|e| IzoneError::from_reqwest(&base_url, e))?;
+
+        Ok(AsyncIzoneClient {
+            http,
+            base_url,
+            retries: options.retries,
+            retry_backoff: options.retry_backoff,
+            check_lock: options.check_lock,
+        })
+    }
+
+    /// The controller base URL this client talks to.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Makes a POST request to the iZone API query endpoint.
+    pub async fn query(&self, payload: Value) -> Result<Value> {
+        self.query_as(payload, "query").await
+    }
+
+    /// Makes a POST request to the iZone API command endpoint.
+    ///
+    /// Only idempotent commands (see [`api::is_idempotent`]) are retried.
+    /// With [`ClientOptions::check_lock`] the system is queried first.
+    pub async fn command(&self, payload: Value) -> Result<()> {
+        if self.check_lock && !api::is_lock_command(&payload) && self.system().await?.lock_status == 1 {
+            return Err(IzoneError::Locked { url: self.base_url.clone() });
+        }
+
+        let command_url = format!("{}{}", self.base_url, COMMAND_URL_SUFFIX);
+        let retries = if api::is_idempotent(&payload) { self.retries } else { 0 };
+        let res_text = self.post_with_retries(&command_url, &payload, retries).await?;
+
+        api::check_command_response(&self.base_url, &res_text)
+    }
+
+    /// Fetches the overall system status (request type 1).
+    pub async fn system(&self) -> Result<SystemV2> {
+        let response: SystemV2Response = self.query_as(api::system_query(), "system status").await?;
+        Ok(response.system_v2)
+    }
+
+    /// Fetches the status of a single zone (request type 2, 0-based index).
+    pub async fn zone(&self, index: u8) -> Result<ZonesV2> {
+        let response: ZonesV2Response = self.query_as(api::zone_query(index), "zone status").await?;
+        Ok(response.zones_v2)
+    }
+
+    /// Fetches a single favourite / schedule (request type 3, 0-based index).
+    pub async fn schedule(&self, index: u8) -> Result<SchedulesV2> {
+        let response: SchedulesResponseWrapper = self.query_as(api::schedule_query(index), "schedule status").await?;
+        Ok(response.schedules_v2)
+    }
+
+    /// Fetches the AC unit fault history (request type 4), in the order the
+    /// controller sends it, including unused slots.
+    pub async fn fault_history(&self) -> Result<Vec<AcFault>> {
+        let response: AcUnitFaultHistResponse = self.query_as(api::fault_history_query(), "fault history").await?;
+        Ok(response.ac_unit_fault_hist_v2.faults)
+    }
+
+    /// Fetches the Temperzone outdoor unit status (request type 5).
+    pub async fn temperzone_info(&self) -> Result<TemperzoneInfoV2> {
+        let response: TemperzoneInfoResponse = self.query_as(api::temperzone_query(), "Temperzone status").await?;
+        Ok(response.temperzone_info_v2)
+    }
+
+    /// Fetches the connected devices and their firmware versions (request type 6).
+    pub async fn firmware(&self) -> Result<Vec<Firmware>> {
+        let response: FirmwareListResponse = self.query_as(api::firmware_query(), "firmware list").await?;
+        Ok(Firmware::parse_list(&response.fmw))
+    }
+
+    /// Turns the AC unit on or off.
+    pub async fn set_system_on(&self, on: bool) -> Result<()> {
+        self.command(api::sys_on_command(on)).await
+    }
+
+    /// Sets the AC unit mode (`SysMode_e`).
+    pub async fn set_system_mode(&self, mode: u8) -> Result<()> {
+        self.command(api::sys_mode_command(mode)).await
+    }
+
+    /// Sets the AC unit fan speed (`SysFan_e`).
+    pub async fn set_system_fan(&self, fan: u8) -> Result<()> {
+        self.command(api::sys_fan_command(fan)).await
+    }
+
+    /// Sets the AC unit setpoint (x100).
+    pub async fn set_system_setpoint(&self, setpoint: u32) -> Result<()> {
+        self.command(api::sys_setpoint_command(setpoint)).await
+    }
+
+    /// Sets the mode of a single zone.
+    pub async fn set_zone_mode(&self, index: u8, mode: ZoneMode) -> Result<()> {
+        self.command(api::zone_mode_command(index, mode)).await
+    }
+
+    /// Sets the setpoint of a single zone (x100).
+    pub async fn set_zone_setpoint(&self, index: u8, setpoint: u32) -> Result<()> {
+        self.command(api::zone_setpoint_command(index, setpoint)).await
+    }
+
+    async fn query_as<T: DeserializeOwned>(&self, payload: Value, what: &str) -> Result<T> {
+        let query_url = format!("{}{}", self.base_url, QUERY_URL_SUFFIX);
+        let body = self.post_with_retries(&query_url, &payload, self.retries).await?;
+
+        api::parse_response(&self.base_url, what, &body)
+    }
+
+    async fn post_with_retries(&self, url: &str, payload: &Value, retries: u32) -> Result<String> {
+        let mut attempt = 0;
+        loop {
+            let result = match self.post(url, payload).await {
+                Ok((status, body)) => api::check_status(&self.base_url, status, &body).map(|_| body),
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Err(e) if attempt < retries && e.is_transient() => {
+                    let delay = api::retry_delay(self.retry_backoff, attempt);
+                    log_retry(url, &e, delay, attempt + 1, retries);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                other => return other,
+            }
+        }
+    }
+
+    async fn post(&self, url: &str, payload: &Value) -> Result<(u16, String)> {
+        log::debug!("POST {} {}", url, api::mask_secrets(&payload.to_string()));
+        let started = Instant::now();
+        let result = self.send(url, payload).await;
+        log_response(url, &result, started.elapsed());
+        result
+    }
+
+    async fn send(&self, url: &str, payload: &Value) -> Result<(u16, String)> {
+        let res = self
+            .http
+            .post(url)
+            .headers(json_headers())
+            .json(payload)
+            .send().await
+            .map_err(|e| IzoneError::from_reqwest(&self.base_url, e))?;
+
+        let status = res.status();
+        let body = res.text().await.map_err(|e| IzoneError::from_reqwest(&self.base_url, e))?;
+        Ok((status.as_u16(), body))
+    }
+}
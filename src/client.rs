@@ -0,0 +1,272 @@
+// izone/src/client.rs
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use reqwest::blocking::Client;
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::api;
+use crate::constants::{
+    COMMAND_URL_SUFFIX, DEFAULT_RETRIES, DEFAULT_RETRY_BACKOFF_MS, DEFAULT_TIMEOUT_MS,
+    QUERY_URL_SUFFIX,
+};
+use crate::error::{IzoneError, Result};
+use crate::models::{
+    AcFault, AcUnitFaultHistResponse, Firmware, FirmwareListResponse, SchedulesResponseWrapper,
+    SchedulesV2, SystemV2, SystemV2Response, TemperzoneInfoResponse, TemperzoneInfoV2, ZoneMode,
+    ZonesV2, ZonesV2Response,
+};
+
+/// Options used when building an [`IzoneClient`] or [`AsyncIzoneClient`](crate::AsyncIzoneClient).
+#[derive(Debug, Clone)]
+pub struct ClientOptions {
+    /// Per-request timeout. `None` uses the reqwest default (no timeout).
+    pub timeout: Option<Duration>,
+    /// How many times a query or idempotent command is retried after a
+    /// connection failure, timeout or 5xx response.
+    pub retries: u32,
+    /// Delay before the first retry, doubled for each further retry.
+    pub retry_backoff: Duration,
+    /// Read `LockStatus` before each command and return
+    /// [`IzoneError::Locked`] instead of sending it while the system is
+    /// locked. `LockSystem` itself is always sent. Off by default, since it
+    /// costs an extra system query per command.
+    pub check_lock: bool,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        ClientOptions {
+            timeout: Some(Duration::from_millis(DEFAULT_TIMEOUT_MS)),
+            retries: DEFAULT_RETRIES,
+            retry_backoff: Duration::from_millis(DEFAULT_RETRY_BACKOFF_MS),
+            check_lock: false,
+        }
+    }
+}
+
+/// Blocking client for a single iZone bridge.
+#[derive(Debug, Clone)]
+pub struct IzoneClient {
+    http: Client,
+    base_url: String,
+    retries: u32,
+    retry_backoff: Duration,
+    check_lock: bool,
+    /// Set once a command has been posted; shared by clones.
+    command_sent: Arc<AtomicBool>,
+}
+
+impl IzoneClient {
+    /// Creates a client for the bridge at `base_url` (e.g. `http://192.168.1.130`).
+    pub fn new(base_url: impl Into<String>) -> Result<Self> {
+        Self::with_options(base_url, ClientOptions::default())
+    }
+
+    /// Creates a client for the bridge at `base_url` using the given options.
+    pub fn with_options(base_url: impl Into<String>, options: ClientOptions) -> Result<Self> {
+        let mut builder = Client::builder();
+        if let Some(timeout) = options.timeout {
+            builder = builder.timeout(timeout);
+        }
+        let base_url = base_url.into().trim_end_matches('/').to_string();
+        let http = builder
+            .build()
+            .map_err(|e| IzoneError::from_reqwest(&base_url, e))?;
+
+        Ok(IzoneClient {
+            http,
+            base_url,
+            retries: options.retries,
+            retry_backoff: options.retry_backoff,
+            check_lock: options.check_lock,
+            command_sent: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    /// The controller base URL this client talks to.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Whether a command has been posted, even one that then failed or timed
+    /// out. Until it has, repeating the whole operation cannot apply anything twice.
+    pub fn command_sent(&self) -> bool {
+        self.command_sent.load(Ordering::Relaxed)
+    }
+
+    /// Makes a POST request to the iZone API query endpoint.
+    pub fn query(&self, payload: Value) -> Result<Value> {
+        self.query_as(payload, "query")
+    }
+
+    /// Makes a POST request to the iZone API command endpoint.
+    ///
+    /// Command responses are often non-JSON or empty, so only success is reported.
+    /// Only idempotent commands (see [`api::is_idempotent`]) are retried.
+    /// With [`ClientOptions::check_lock`] the system is queried first.
+    pub fn command(&self, payload: Value) -> Result<()> {
+        if self.check_lock && !api::is_lock_command(&payload) && self.system()?.lock_status == 1 {
+            return Err(IzoneError::Locked { url: self.base_url.clone() });
+        }
+
+        let command_url = format!("{}{}", self.base_url, COMMAND_URL_SUFFIX);
+        let retries = if api::is_idempotent(&payload) { self.retries } else { 0 };
+        self.command_sent.store(true, Ordering::Relaxed);
+        let res_text = self.post_with_retries(&command_url, &payload, retries)?;
+
+        // Check for common error indicators in the text response
+        api::check_command_response(&self.base_url, &res_text)
+    }
+
+    /// Fetches the overall system status (request type 1).
+    pub fn system(&self) -> Result<SystemV2> {
+        let response: SystemV2Response = self.query_as(api::system_query(), "system status")?;
+        Ok(response.system_v2)
+    }
+
+    /// Fetches the status of a single zone (request type 2, 0-based index).
+    pub fn zone(&self, index: u8) -> Result<ZonesV2> {
+        let response: ZonesV2Response = self.query_as(api::zone_query(index), "zone status")?;
+        Ok(response.zones_v2)
+    }
+
+    /// Fetches a single favourite / schedule (request type 3, 0-based index).
+    pub fn schedule(&self, index: u8) -> Result<SchedulesV2> {
+        let response: SchedulesResponseWrapper = self.query_as(api::schedule_query(index), "schedule status")?;
+        Ok(response.schedules_v2)
+    }
+
+    /// Fetches the AC unit fault history (request type 4), in the order the
+    /// controller sends it, including unused slots.
+    pub fn fault_history(&self) -> Result<Vec<AcFault>> {
+        let response: AcUnitFaultHistResponse = self.query_as(api::fault_history_query(), "fault history")?;
+        Ok(response.ac_unit_fault_hist_v2.faults)
+    }
+
+    /// Fetches the Temperzone outdoor unit status (request type 5).
+    pub fn temperzone_info(&self) -> Result<TemperzoneInfoV2> {
+        let response: TemperzoneInfoResponse = self.query_as(api::temperzone_query(), "Temperzone status")?;
+        Ok(response.temperzone_info_v2)
+    }
+
+    /// Fetches the connected devices and their firmware versions (request type 6).
+    pub fn firmware(&self) -> Result<Vec<Firmware>> {
+        let response: FirmwareListResponse = self.query_as(api::firmware_query(), "firmware list")?;
+        Ok(Firmware::parse_list(&response.fmw))
+    }
+
+    /// Turns the AC unit on or off.
+    pub fn set_system_on(&self, on: bool) -> Result<()> {
+        self.command(api::sys_on_command(on))
+    }
+
+    /// Sets the AC unit mode (`SysMode_e`).
+    pub fn set_system_mode(&self, mode: u8) -> Result<()> {
+        self.command(api::sys_mode_command(mode))
+    }
+
+    /// Sets the AC unit fan speed (`SysFan_e`).
+    pub fn set_system_fan(&self, fan: u8) -> Result<()> {
+        self.command(api::sys_fan_command(fan))
+    }
+
+    /// Sets the AC unit setpoint (x100).
+    pub fn set_system_setpoint(&self, setpoint: u32) -> Result<()> {
+        self.command(api::sys_setpoint_command(setpoint))
+    }
+
+    /// Sets the mode of a single zone.
+    pub fn set_zone_mode(&self, index: u8, mode: ZoneMode) -> Result<()> {
+        self.command(api::zone_mode_command(index, mode))
+    }
+
+    /// Sets the setpoint of a single zone (x100).
+    pub fn set_zone_setpoint(&self, index: u8, setpoint: u32) -> Result<()> {
+        self.command(api::zone_setpoint_command(index, setpoint))
+    }
+
+    /// Sends a query and parses the body straight into `T`, so parse errors keep the raw text.
+    fn query_as<T: DeserializeOwned>(&self, payload: Value, what: &str) -> Result<T> {
+        let query_url = format!("{}{}", self.base_url, QUERY_URL_SUFFIX);
+        let body = self.post_with_retries(&query_url, &payload, self.retries)?;
+
+        api::parse_response(&self.base_url, what, &body)
+    }
+
+    /// Posts `payload`, retrying transient failures up to `retries` times.
+    /// Returns the body of the first successful response.
+    fn post_with_retries(&self, url: &str, payload: &Value, retries: u32) -> Result<String> {
+        let mut attempt = 0;
+        loop {
+            let result = self
+                .post(url, payload)
+                .and_then(|(status, body)| api::check_status(&self.base_url, status, &body).map(|_| body));
+
+            match result {
+                Err(e) if attempt < retries && e.is_transient() => {
+                    let delay = api::retry_delay(self.retry_backoff, attempt);
+                    log_retry(url, &e, delay, attempt + 1, retries);
+                    std::thread::sleep(delay);
+                    attempt += 1;
+                }
+                other => return other,
+            }
+        }
+    }
+
+    fn post(&self, url: &str, payload: &Value) -> Result<(u16, String)> {
+        log::debug!("POST {} {}", url, api::mask_secrets(&payload.to_string()));
+        let started = Instant::now();
+        let result = self.send(url, payload);
+        log_response(url, &result, started.elapsed());
+        result
+    }
+
+    fn send(&self, url: &str, payload: &Value) -> Result<(u16, String)> {
+        let res = self
+            .http
+            .post(url)
+            .headers(json_headers())
+            .json(payload)
+            .send()
+            .map_err(|e| IzoneError::from_reqwest(&self.base_url, e))?;
+
+        let status = res.status();
+        let body = res.text().map_err(|e| IzoneError::from_reqwest(&self.base_url, e))?;
+        Ok((status.as_u16(), body))
+    }
+}
+
+/// Logs the outcome of a request with its timing (debug level).
+pub(crate) fn log_response(url: &str, result: &Result<(u16, String)>, elapsed: Duration) {
+    match result {
+        Ok((status, body)) => log::debug!("Response {} in {} ms: {}", status, elapsed.as_millis(), api::mask_secrets(body)),
+        Err(e) => log::debug!("POST {} failed after {} ms: {}", url, elapsed.as_millis(), short_reason(e)),
+    }
+}
+
+/// Logs a retry (info level, shown with `-v`).
+pub(crate) fn log_retry(url: &str, error: &IzoneError, delay: Duration, attempt: u32, retries: u32) {
+    log::info!(
+        "Request to {} failed ({}), retry {}/{} in {} ms",
+        url, short_reason(error), attempt, retries, delay.as_millis()
+    );
+}
+
+/// The "Error details" line of an error, for one-line log messages.
+fn short_reason(error: &IzoneError) -> String {
+    let message = error.to_string();
+    let last = message.lines().last().unwrap_or_default();
+    last.trim_start_matches("Error details: ").to_string()
+}
+
+pub(crate) fn json_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    headers
+}
@@ -0,0 +1,231 @@
+// izone/src/commands/config_file.rs
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use colored::Colorize;
+use serde::Serialize;
+
+use izone::constants::{
+    self, Config, DEFAULT_IZONE_IP, DEFAULT_RETRIES, DEFAULT_RETRY_BACKOFF_MS, DEFAULT_TIMEOUT_MS,
+};
+use izone::discovery;
+use izone::helpers::get_visible_length;
+use izone::{IzoneClient, IzoneError, Result, ZoneMap};
+
+use crate::commands::output::{print_record, OutputFormat};
+
+/// The starter file written by `izone config init`.
+const STARTER_CONFIG: &str = include_str!("../../izone.toml.example");
+
+/// One config search path and whether it was used.
+#[derive(Serialize)]
+struct SearchPath {
+    path: String,
+    status: &'static str,
+}
+
+/// What `izone config show` reports.
+#[derive(Serialize)]
+struct ConfigView {
+    /// The file the config came from, `None` when the defaults are used.
+    source: Option<String>,
+    search_paths: Vec<SearchPath>,
+    profile: Option<String>,
+    env_overrides: Vec<String>,
+    izone_ip: String,
+    timeout_ms: u64,
+    retries: u32,
+    retry_backoff_ms: u64,
+    output: String,
+    /// `None` when zone names are discovered from the controller.
+    zones: Option<BTreeMap<String, u8>>,
+}
+
+/// Prints the effective config (after profile, environment and command-line
+/// overrides) and the file it came from.
+pub fn show_config(config: &Config, source: Option<&Path>, env_overrides: &[&str], output: OutputFormat) -> Result<()> {
+    let search_paths = constants::config_search_paths()
+        .into_iter()
+        .map(|path| SearchPath {
+            status: if Some(path.as_path()) == source {
+                "used"
+            } else if path.exists() {
+                "ignored (lower priority)"
+            } else {
+                "not found"
+            },
+            path: path.display().to_string(),
+        })
+        .collect();
+
+    let view = ConfigView {
+        source: source.map(|p| p.display().to_string()),
+        search_paths,
+        profile: config.active_profile.clone(),
+        env_overrides: env_overrides.iter().map(|v| v.to_string()).collect(),
+        izone_ip: config.izone_ip(),
+        timeout_ms: config.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS),
+        retries: config.retries.unwrap_or(DEFAULT_RETRIES),
+        retry_backoff_ms: config.retry_backoff_ms.unwrap_or(DEFAULT_RETRY_BACKOFF_MS),
+        output: config.output.clone().unwrap_or_else(|| "table".to_string()).to_lowercase(),
+        zones: config.zones.clone(),
+    };
+
+    if output != OutputFormat::Table {
+        return print_record(output, &view);
+    }
+
+    const BOX_WIDTH: usize = 70;
+    const PADDING_WIDTH: usize = BOX_WIDTH - 2;
+    const LABEL_WIDTH: usize = 20;
+
+    let print_line = |label: &str, value: String| {
+        let padding = PADDING_WIDTH.saturating_sub(LABEL_WIDTH + get_visible_length(&value));
+        println!("║ {:<LABEL_WIDTH$}{}{} ║", label, value, " ".repeat(padding));
+    };
+
+    println!("╔{}╗", "═".repeat(BOX_WIDTH));
+    println!("║ {:^PADDING_WIDTH$} ║", "EFFECTIVE CONFIG");
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+    print_line("Source:", view.source.clone().map_or("none (defaults)".yellow().to_string(), |s| s.green().to_string()));
+    print_line("Profile:", view.profile.clone().unwrap_or_else(|| "-".to_string()));
+    print_line(
+        "Env overrides:",
+        if view.env_overrides.is_empty() { "-".to_string() } else { view.env_overrides.join(", ").cyan().to_string() },
+    );
+    print_line("izone_ip:", view.izone_ip.clone());
+    print_line("timeout_ms:", view.timeout_ms.to_string());
+    print_line("retries:", view.retries.to_string());
+    print_line("retry_backoff_ms:", view.retry_backoff_ms.to_string());
+    print_line("output:", view.output.clone());
+    match &view.zones {
+        None => print_line("zones:", "discovered from the controller".to_string()),
+        Some(zones) => {
+            let mut zones: Vec<(&String, &u8)> = zones.iter().collect();
+            zones.sort_by_key(|(_, index)| **index);
+            for (i, (name, index)) in zones.iter().enumerate() {
+                print_line(if i == 0 { "zones:" } else { "" }, format!("{} = {}", name, index));
+            }
+        }
+    }
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+    println!("║ {:^PADDING_WIDTH$} ║", "SEARCH PATHS");
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+    for (i, search_path) in view.search_paths.iter().enumerate() {
+        let status = match search_path.status {
+            "used" => search_path.status.green().to_string(),
+            "not found" => search_path.status.normal().to_string(),
+            _ => search_path.status.yellow().to_string(),
+        };
+        print_line(&format!("{}. {}", i + 1, status), search_path.path.clone());
+    }
+    println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
+}
+
+/// Parses the config file strictly: syntax errors, unknown keys and invalid
+/// values are all reported with their line numbers.
+pub fn check_config(explicit: Option<&Path>) -> Result<()> {
+    let path = match explicit {
+        Some(path) => path.to_path_buf(),
+        None => constants::config_search_paths().into_iter().find(|p| p.exists()).ok_or_else(|| {
+            let searched: Vec<String> = constants::config_search_paths().iter().map(|p| p.display().to_string()).collect();
+            IzoneError::validation(format!(
+                "No config file found. Searched:\n  {}\nRun 'izone config init' to create one.",
+                searched.join("\n  ")
+            ))
+        })?,
+    };
+
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| IzoneError::validation(format!("Cannot read config file '{}': {}", path.display(), e)))?;
+
+    let issues = match constants::parse_config(&contents) {
+        Ok((config, mut issues)) => {
+            issues.extend(constants::check_config(&config, &contents));
+            issues.sort_by_key(|issue| issue.line);
+            issues
+        }
+        Err(issue) => vec![issue],
+    };
+
+    if issues.is_empty() {
+        println!("{} {} is valid.", "✓".green(), path.display());
+        return Ok(());
+    }
+
+    for issue in &issues {
+        println!("{} {}: {}", "✗".red(), path.display(), issue);
+    }
+    Err(IzoneError::validation(format!(
+        "{} problem(s) found in {}.",
+        issues.len(),
+        path.display()
+    )))
+}
+
+/// Writes a starter config file. With `discover`, the first iZone V2 bridge
+/// that answers on `target` fills in `izone_ip` and its zone names.
+pub fn init_config(explicit: Option<&Path>, discover: bool, target: &str, force: bool) -> Result<()> {
+    let path: PathBuf = match explicit {
+        Some(path) => path.to_path_buf(),
+        None => constants::config_search_paths()
+            .into_iter()
+            .next()
+            .ok_or_else(|| IzoneError::validation("Cannot find a config directory; use --config <path>."))?,
+    };
+    if path.exists() && !force {
+        return Err(IzoneError::validation(format!(
+            "{} already exists. Use --force to overwrite it.",
+            path.display()
+        )));
+    }
+
+    let mut contents = STARTER_CONFIG.to_string();
+    if discover {
+        let bridges = discovery::discover(target, Duration::from_millis(discovery::DEFAULT_DISCOVERY_WAIT_MS))?;
+        match bridges.iter().find(|b| b.supports_v2()) {
+            Some(bridge) => {
+                println!("Found bridge {} at {}.", bridge.id.cyan(), bridge.ip.green());
+                contents = contents.replacen(
+                    &format!("izone_ip = \"{}\"", DEFAULT_IZONE_IP),
+                    &format!("izone_ip = \"{}\"", bridge.url()),
+                    1,
+                );
+                match IzoneClient::new(bridge.url()).and_then(|client| ZoneMap::discover(&client)) {
+                    Ok(zones) => contents.push_str(&zones_table(&zones)),
+                    Err(e) => log::warn!("Could not read zone names from {}: {}", bridge.ip, e),
+                }
+            }
+            None => println!("{}", "No iZone V2 bridge answered; using the default IP.".yellow()),
+        }
+    }
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .map_err(|e| IzoneError::validation(format!("Cannot create {}: {}", parent.display(), e)))?;
+    }
+    fs::write(&path, contents)
+        .map_err(|e| IzoneError::validation(format!("Cannot write {}: {}", path.display(), e)))?;
+    println!("Wrote {}.", path.display().to_string().green());
+
+    Ok(())
+}
+
+/// A `[zones]` table for the discovered zone names.
+fn zones_table(zones: &ZoneMap) -> String {
+    let mut table = String::from("\n# Zone names read from the controller\n[zones]\n");
+    for (name, index) in zones.entries() {
+        let bare = !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if bare {
+            table.push_str(&format!("{} = {}\n", name, index));
+        } else {
+            table.push_str(&format!("\"{}\" = {}\n", name.replace('"', "\\\""), index));
+        }
+    }
+    table
+}
@@ -0,0 +1,55 @@
+// izone/src/commands/devices.rs
+
+use colored::Colorize;
+
+use izone::helpers::{get_sys_type_text, get_visible_length};
+use izone::report::DeviceReport;
+use izone::{IzoneClient, Result};
+
+use crate::commands::output::{print_record, OutputFormat};
+
+/// Prints the controller hardware and the firmware of every connected module,
+/// e.g. for a support ticket.
+pub fn list_devices(client: &IzoneClient, output: OutputFormat) -> Result<()> {
+    let sys_v2 = client.system()?;
+    let firmware = client.firmware()?;
+    let report = DeviceReport::new(&sys_v2, firmware);
+
+    if output != OutputFormat::Table {
+        return print_record(output, &report);
+    }
+
+    const BOX_WIDTH: usize = 60;
+    const PADDING_WIDTH: usize = BOX_WIDTH - 2;
+    const LABEL_WIDTH: usize = 26;
+
+    let print_line = |label: &str, value: String| {
+        let padding = PADDING_WIDTH.saturating_sub(LABEL_WIDTH + get_visible_length(&value));
+        println!("║ {:<LABEL_WIDTH$}{}{} ║", label, value, " ".repeat(padding));
+    };
+
+    println!("╔{}╗", "═".repeat(BOX_WIDTH));
+    println!("║ {:^PADDING_WIDTH$} ║", "SYSTEM HARDWARE");
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+    print_line("Controller (CpuType):", report.cpu_type.cyan().to_string());
+    print_line("Make (OemMake):", report.oem_make.clone());
+    print_line("AC unit (detected):", report.ac_unit_brand.cyan().to_string());
+    print_line("AC unit (configured):", report.ac_unit_brand_set.clone());
+    print_line("System type (SysType):", get_sys_type_text(report.sys_type));
+    print_line("System number:", report.sys_no.to_string());
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+    println!("║ {:^PADDING_WIDTH$} ║", "FIRMWARE");
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+    if report.firmware.is_empty() {
+        println!("║ {:^PADDING_WIDTH$} ║", "No devices reported.");
+    } else {
+        print_line("DEVICE", "VERSION".to_string());
+        for module in &report.firmware {
+            let version = if module.version.is_empty() { "-".to_string() } else { module.version.green().to_string() };
+            print_line(&module.device, version);
+        }
+    }
+    println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
+}
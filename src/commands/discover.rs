@@ -0,0 +1,71 @@
+// izone/src/commands/discover.rs
+
+use std::time::Duration;
+
+use colored::Colorize;
+use izone::discovery::{self, Bridge, BROADCAST_TARGET};
+use izone::helpers::get_visible_length;
+use izone::Result;
+
+use crate::commands::output::{print_records, OutputFormat};
+
+/// Broadcasts "IASD" (or sends it to `target`) and lists every bridge that answers.
+/// A broadcast search also refreshes the cache used by `izone_ip = "auto"`
+/// for the active profile.
+pub fn discover_bridges(target: &str, wait_ms: u64, profile: Option<&str>, output: OutputFormat) -> Result<()> {
+    let bridges = discovery::discover(target, Duration::from_millis(wait_ms))?;
+
+    if target == BROADCAST_TARGET
+        && let Some(bridge) = bridges.iter().find(|b| b.supports_v2())
+    {
+        discovery::save_cached_url(profile, &bridge.url());
+    }
+
+    if output != OutputFormat::Table {
+        return print_records(output, &bridges);
+    }
+
+    const ID_COL_WIDTH: usize = 12;
+    const IP_COL_WIDTH: usize = 16;
+    const PORT_COL_WIDTH: usize = 6;
+    const CAPS_COL_WIDTH: usize = 40;
+    const TOTAL_WIDTH: usize = ID_COL_WIDTH + 1 + IP_COL_WIDTH + 1 + PORT_COL_WIDTH + 1 + CAPS_COL_WIDTH;
+
+    println!("╔{}╗", "═".repeat(TOTAL_WIDTH + 2));
+    println!("║ {:^width$} ║", "IZONE BRIDGES", width = TOTAL_WIDTH);
+    println!("╠{}╣", "═".repeat(TOTAL_WIDTH + 2));
+
+    if bridges.is_empty() {
+        println!("║ {:^width$} ║", format!("No bridges answered within {} ms.", wait_ms), width = TOTAL_WIDTH);
+    } else {
+        println!(
+            "║ {:<ID_COL_WIDTH$} {:<IP_COL_WIDTH$} {:<PORT_COL_WIDTH$} {:<CAPS_COL_WIDTH$} ║",
+            "ID", "IP", "PORT", "CAPABILITIES"
+        );
+        println!("╠{}╣", "═".repeat(TOTAL_WIDTH + 2));
+        for bridge in &bridges {
+            let caps = capabilities_text(bridge);
+            println!(
+                "║ {:<ID_COL_WIDTH$} {:<IP_COL_WIDTH$} {:<PORT_COL_WIDTH$} {}{} ║",
+                bridge.id,
+                bridge.ip,
+                bridge.port,
+                caps,
+                " ".repeat(CAPS_COL_WIDTH.saturating_sub(get_visible_length(&caps)))
+            );
+        }
+    }
+    println!("╚{}╝", "═".repeat(TOTAL_WIDTH + 2));
+
+    Ok(())
+}
+
+/// Capabilities with iZoneV2 (the API this tool needs) highlighted.
+fn capabilities_text(bridge: &Bridge) -> String {
+    let caps: Vec<String> = bridge
+        .capabilities
+        .iter()
+        .map(|c| if c == "iZoneV2" { c.green().to_string() } else { c.to_string() })
+        .collect();
+    if caps.is_empty() { "none".red().to_string() } else { caps.join(", ") }
+}
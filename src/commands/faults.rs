@@ -0,0 +1,60 @@
+// izone/src/commands/faults.rs
+
+use colored::Colorize;
+
+use izone::helpers::get_visible_length;
+use izone::report::FaultReport;
+use izone::{IzoneClient, Result};
+
+use crate::commands::output::{print_records, OutputFormat};
+
+/// Lists the AC unit fault history (request type 4), skipping unused slots.
+pub fn list_faults(client: &IzoneClient, output: OutputFormat) -> Result<()> {
+    let faults = client.fault_history()?;
+    log::trace!("Fault history: {:#?}", faults);
+
+    let rows: Vec<FaultReport> = faults
+        .iter()
+        .enumerate()
+        .filter(|(_, fault)| !fault.is_empty())
+        .map(|(i, fault)| FaultReport::new(i as u8 + 1, fault))
+        .collect();
+
+    if output != OutputFormat::Table {
+        return print_records(output, &rows);
+    }
+
+    const SLOT_COL_WIDTH: usize = 4;
+    const CODE_COL_WIDTH: usize = 6;
+    const TIME_COL_WIDTH: usize = 18;
+    const TOTAL_WIDTH: usize = SLOT_COL_WIDTH + 1 + CODE_COL_WIDTH + 1 + TIME_COL_WIDTH;
+
+    println!("╔{}╗", "═".repeat(TOTAL_WIDTH + 2));
+    println!("║ {:^width$} ║", "AC UNIT FAULT HISTORY", width = TOTAL_WIDTH);
+    println!("╠{}╣", "═".repeat(TOTAL_WIDTH + 2));
+
+    if rows.is_empty() {
+        println!("║ {:^width$} ║", "No faults recorded.", width = TOTAL_WIDTH);
+    } else {
+        println!(
+            "║ {:<SLOT_COL_WIDTH$} {:<CODE_COL_WIDTH$} {:<TIME_COL_WIDTH$} ║",
+            "#", "CODE", "TIME"
+        );
+        println!("╠{}╣", "═".repeat(TOTAL_WIDTH + 2));
+        for row in &rows {
+            let code = row.code.red().to_string();
+            let time = row.time.clone().unwrap_or_else(|| "invalid date".yellow().to_string());
+            println!(
+                "║ {:<SLOT_COL_WIDTH$} {:<code_width$} {:<time_width$} ║",
+                row.slot,
+                code,
+                time,
+                code_width = CODE_COL_WIDTH + code.len() - get_visible_length(&code),
+                time_width = TIME_COL_WIDTH + time.len() - get_visible_length(&time),
+            );
+        }
+    }
+    println!("╚{}╝", "═".repeat(TOTAL_WIDTH + 2));
+
+    Ok(())
+}
@@ -0,0 +1,176 @@
+// izone/src/commands/gasheat.rs
+
+use colored::Colorize;
+
+use izone::api;
+use izone::helpers::{get_gas_heat_type_text, get_gas_heat_type_value, get_visible_length, GAS_HEAT_TYPE_NAMES};
+use izone::models::GasHeat;
+use izone::report::GasHeatReport;
+use izone::{IzoneClient, IzoneError, Result};
+
+use crate::commands::output::{print_record, OutputFormat};
+
+const BOX_WIDTH: usize = 60;
+const PADDING_WIDTH: usize = BOX_WIDTH - 2;
+const LABEL_WIDTH: usize = 26;
+
+fn print_line(label: &str, value: String) {
+    let padding = PADDING_WIDTH.saturating_sub(LABEL_WIDTH + get_visible_length(&value));
+    println!("║ {:<LABEL_WIDTH$}{}{} ║", label, value, " ".repeat(padding));
+}
+
+fn print_settings(title: &str, gas: &GasHeat) {
+    let minutes = |value: u8| format!("{} min", value).cyan().to_string();
+    let fan = |cycle: u8| match cycle {
+        0 => "Run continuously".to_string(),
+        1 => "Cycle with unit".to_string(),
+        _ => format!("CycleFan({})", cycle).yellow().to_string(),
+    };
+
+    println!("╔{}╗", "═".repeat(BOX_WIDTH));
+    println!("║ {:^PADDING_WIDTH$} ║", title);
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+    print_line("Type:", format!("{} ({})", get_gas_heat_type_text(gas.gas_type), gas.gas_type));
+    print_line("Minimum run time:", minutes(gas.min_run_time));
+    print_line("Anti-cycle time:", minutes(gas.anticycle_time));
+    print_line("Stage offset:", format!("{:.1}°C", gas.stage_offset as f64 / 10.0).cyan().to_string());
+    print_line("Stage delay:", minutes(gas.stage_delay));
+    print_line("Fan in cool mode:", fan(gas.cycle_fan_cool));
+    print_line("Fan in heat mode:", fan(gas.cycle_fan_heat));
+    println!("╚{}╝", "═".repeat(BOX_WIDTH));
+}
+
+/// Shows the universal (gas heat) controller settings.
+pub fn show_gas_heat(client: &IzoneClient, output: OutputFormat) -> Result<()> {
+    let gas = client.system()?.gas_heat;
+    log::trace!("Gas heat settings: {:#?}", gas);
+
+    if output != OutputFormat::Table {
+        return print_record(output, &GasHeatReport::from(&gas));
+    }
+    print_settings("GAS HEAT SETTINGS", &gas);
+    Ok(())
+}
+
+/// Changes to the gas heat settings; `None` keeps the current value.
+#[derive(Debug)]
+pub struct GasHeatChanges<'a> {
+    pub gas_type: Option<&'a str>,
+    pub min_run_time: Option<u8>,
+    pub anticycle_time: Option<u8>,
+    pub stage_offset: Option<f32>,
+    pub stage_delay: Option<u8>,
+    pub cycle_fan_cool: Option<bool>,
+    pub cycle_fan_heat: Option<bool>,
+}
+
+impl GasHeatChanges<'_> {
+    fn is_empty(&self) -> bool {
+        self.gas_type.is_none()
+            && self.min_run_time.is_none()
+            && self.anticycle_time.is_none()
+            && self.stage_offset.is_none()
+            && self.stage_delay.is_none()
+            && self.cycle_fan_cool.is_none()
+            && self.cycle_fan_heat.is_none()
+    }
+}
+
+/// Checks a merged setting against its documented range. Values the user did
+/// not pass come from the controller, so the error says where to fix them.
+fn check_range(label: &str, flag: &str, value: u8, min: u8, max: u8, given: bool) -> Result<()> {
+    if (min..=max).contains(&value) {
+        return Ok(());
+    }
+    let message = if given {
+        format!("{} must be {}-{}, got {}.", label, min, max, value)
+    } else {
+        format!(
+            "The controller reports {} = {}, outside {}-{}. All seven settings are sent together, so pass {} as well.",
+            label, value, min, max, flag
+        )
+    };
+    Err(IzoneError::validation(message))
+}
+
+/// Merges `changes` into the current gas heat settings, validates the result
+/// and sends all seven fields with `GasHeatSettings`, then reads them back.
+pub fn set_gas_heat(client: &IzoneClient, changes: GasHeatChanges, output: OutputFormat) -> Result<()> {
+    if changes.is_empty() {
+        return Err(IzoneError::validation(
+            "Nothing to change. Pass at least one of --type, --min-run, --anticycle, --stage-offset, --stage-delay, --cycle-fan-cool or --cycle-fan-heat.",
+        ));
+    }
+
+    let gas_type = changes
+        .gas_type
+        .map(|name| {
+            get_gas_heat_type_value(name).ok_or_else(|| {
+                IzoneError::validation(format!(
+                    "Unknown gas heat type '{}'.\nAvailable types (or 0-{}): {}.",
+                    name,
+                    GAS_HEAT_TYPE_NAMES.len() - 1,
+                    GAS_HEAT_TYPE_NAMES.join(", ")
+                ))
+            })
+        })
+        .transpose()?;
+    let stage_offset = changes
+        .stage_offset
+        .map(|celsius| {
+            let value = (celsius * 10.0).round();
+            if !(20.0..=50.0).contains(&value) {
+                return Err(IzoneError::validation(format!("Stage offset must be 2.0-5.0°C, got {:.1}°C.", celsius)));
+            }
+            Ok(value as u8)
+        })
+        .transpose()?;
+
+    let current = client.system()?.gas_heat;
+    log::trace!("Gas heat settings: {:#?}", current);
+
+    let wanted = GasHeat {
+        gas_type: gas_type.unwrap_or(current.gas_type),
+        min_run_time: changes.min_run_time.unwrap_or(current.min_run_time),
+        anticycle_time: changes.anticycle_time.unwrap_or(current.anticycle_time),
+        stage_offset: stage_offset.unwrap_or(current.stage_offset),
+        stage_delay: changes.stage_delay.unwrap_or(current.stage_delay),
+        cycle_fan_cool: changes.cycle_fan_cool.map(u8::from).unwrap_or(current.cycle_fan_cool),
+        cycle_fan_heat: changes.cycle_fan_heat.map(u8::from).unwrap_or(current.cycle_fan_heat),
+    };
+
+    check_range("Type", "--type", wanted.gas_type, 0, GAS_HEAT_TYPE_NAMES.len() as u8 - 1, gas_type.is_some())?;
+    check_range("MinRunTime", "--min-run", wanted.min_run_time, 2, 10, changes.min_run_time.is_some())?;
+    check_range("AnticycleTime", "--anticycle", wanted.anticycle_time, 2, 10, changes.anticycle_time.is_some())?;
+    check_range("StageOffset", "--stage-offset", wanted.stage_offset, 20, 50, stage_offset.is_some())?;
+    check_range("StageDelay", "--stage-delay", wanted.stage_delay, 5, 15, changes.stage_delay.is_some())?;
+    check_range("CycleFanCool", "--cycle-fan-cool", wanted.cycle_fan_cool, 0, 1, changes.cycle_fan_cool.is_some())?;
+    check_range("CycleFanHeat", "--cycle-fan-heat", wanted.cycle_fan_heat, 0, 1, changes.cycle_fan_heat.is_some())?;
+
+    client.command(api::gas_heat_settings_command(&wanted))?;
+
+    let gas = client.system()?.gas_heat;
+    let mismatch = [
+        ("Type", wanted.gas_type, gas.gas_type),
+        ("MinRunTime", wanted.min_run_time, gas.min_run_time),
+        ("AnticycleTime", wanted.anticycle_time, gas.anticycle_time),
+        ("StageOffset", wanted.stage_offset, gas.stage_offset),
+        ("StageDelay", wanted.stage_delay, gas.stage_delay),
+        ("CycleFanCool", wanted.cycle_fan_cool, gas.cycle_fan_cool),
+        ("CycleFanHeat", wanted.cycle_fan_heat, gas.cycle_fan_heat),
+    ]
+    .into_iter()
+    .find(|(_, wanted, actual)| wanted != actual);
+    if let Some((field, wanted, actual)) = mismatch {
+        return Err(IzoneError::Controller {
+            url: client.base_url().to_string(),
+            message: format!("Gas heat {} still reports {} after setting it to {}.", field, actual, wanted),
+        });
+    }
+
+    if output != OutputFormat::Table {
+        return print_record(output, &GasHeatReport::from(&gas));
+    }
+    print_settings("GAS HEAT SETTINGS (UPDATED)", &gas);
+    Ok(())
+}
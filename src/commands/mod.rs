@@ -2,4 +2,12 @@
 
 pub mod system;
 pub mod zones;
-pub mod schedules; // New: Declare the schedules module
\ No newline at end of file
+pub mod schedules; // New: Declare the schedules module
+pub mod output;
+pub mod discover;
+pub mod profile;
+pub mod config_file;
+pub mod faults;
+pub mod temperzone;
+pub mod devices;
+pub mod gasheat;
\ No newline at end of file
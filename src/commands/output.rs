@@ -0,0 +1,104 @@
+// izone/src/commands/output.rs
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use izone::{IzoneError, Result};
+
+/// Output format selected with the global `--output` flag.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Box-drawn, coloured tables (default).
+    #[default]
+    Table,
+    Json,
+    Yaml,
+    Csv,
+}
+
+/// Prints a single report in a machine-readable format.
+pub fn print_record<T: Serialize>(format: OutputFormat, record: &T) -> Result<()> {
+    match format {
+        OutputFormat::Csv => print_csv(std::slice::from_ref(record)),
+        _ => print_value(format, record),
+    }
+}
+
+/// Prints a list of reports in a machine-readable format.
+pub fn print_records<T: Serialize>(format: OutputFormat, records: &[T]) -> Result<()> {
+    match format {
+        OutputFormat::Csv => print_csv(records),
+        _ => print_value(format, &records),
+    }
+}
+
+fn print_value<T: Serialize + ?Sized>(format: OutputFormat, value: &T) -> Result<()> {
+    let text = match format {
+        OutputFormat::Yaml => serde_yaml::to_string(value).map_err(output_error)?,
+        _ => serde_json::to_string_pretty(value).map_err(output_error)?,
+    };
+    println!("{}", text.trim_end());
+    Ok(())
+}
+
+/// CSV needs flat rows: nested objects become `parent.child` columns and
+/// lists are joined with ';' (e.g. `days` = `mon;tue;wed`). Columns missing
+/// from a row are left empty.
+fn print_csv<T: Serialize>(records: &[T]) -> Result<()> {
+    let mut rows = Vec::new();
+    let mut header: Vec<String> = Vec::new();
+    for record in records {
+        let mut row = Map::new();
+        flatten("", serde_json::to_value(record).map_err(output_error)?, &mut row);
+        for key in row.keys() {
+            if !header.contains(key) {
+                header.push(key.clone());
+            }
+        }
+        rows.push(row);
+    }
+
+    let mut writer = csv::Writer::from_writer(std::io::stdout());
+    writer.write_record(&header).map_err(output_error)?;
+    for row in &rows {
+        writer
+            .write_record(header.iter().map(|key| row.get(key).map(cell).unwrap_or_default()))
+            .map_err(output_error)?;
+    }
+    writer.flush().map_err(output_error)
+}
+
+fn flatten(prefix: &str, value: Value, row: &mut Map<String, Value>) {
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields {
+                let name = if prefix.is_empty() { key } else { format!("{}.{}", prefix, key) };
+                flatten(&name, field, row);
+            }
+        }
+        Value::Array(items) if items.iter().any(|item| item.is_object()) => {
+            for (i, item) in items.into_iter().enumerate() {
+                flatten(&format!("{}.{}", prefix, i), item, row);
+            }
+        }
+        Value::Array(items) => {
+            let joined = items.iter().map(cell).collect::<Vec<_>>().join(";");
+            row.insert(prefix.to_string(), Value::String(joined));
+        }
+        other => {
+            row.insert(prefix.to_string(), other);
+        }
+    }
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn output_error(e: impl std::fmt::Display) -> IzoneError {
+    IzoneError::validation(format!("Failed to write output: {}", e))
+}
@@ -0,0 +1,87 @@
+// izone/src/commands/profile.rs
+
+use colored::Colorize;
+use serde::Serialize;
+
+use izone::constants::{Config, DEFAULT_IZONE_IP};
+use izone::Result;
+
+use crate::commands::output::{print_records, OutputFormat};
+
+/// One row of `izone profile list`.
+#[derive(Serialize)]
+struct ProfileRow {
+    name: String,
+    izone_ip: String,
+    /// Number of zones in the profile's `[zones]` table, `None` when discovered.
+    zones: Option<usize>,
+    default: bool,
+    active: bool,
+}
+
+/// Lists the `[profiles.<name>]` tables, marking the default and the one in use.
+pub fn list_profiles(config: &Config, active: Option<&str>, output: OutputFormat) -> Result<()> {
+    let active = active.or(config.default_profile.as_deref());
+    let rows: Vec<ProfileRow> = config
+        .profiles
+        .iter()
+        .map(|(name, profile)| ProfileRow {
+            name: name.clone(),
+            izone_ip: profile
+                .izone_ip
+                .clone()
+                .or_else(|| config.izone_ip.clone())
+                .unwrap_or_else(|| DEFAULT_IZONE_IP.to_string()),
+            zones: profile.zones.as_ref().map(|zones| zones.len()),
+            default: config.default_profile.as_deref() == Some(name.as_str()),
+            active: active == Some(name.as_str()),
+        })
+        .collect();
+
+    if output != OutputFormat::Table {
+        return print_records(output, &rows);
+    }
+
+    const NAME_COL_WIDTH: usize = 16;
+    const IP_COL_WIDTH: usize = 30;
+    const ZONES_COL_WIDTH: usize = 12;
+    const FLAGS_COL_WIDTH: usize = 16;
+    const TOTAL_WIDTH: usize = NAME_COL_WIDTH + 1 + IP_COL_WIDTH + 1 + ZONES_COL_WIDTH + 1 + FLAGS_COL_WIDTH;
+
+    println!("╔{}╗", "═".repeat(TOTAL_WIDTH + 2));
+    println!("║ {:^width$} ║", "PROFILES", width = TOTAL_WIDTH);
+    println!("╠{}╣", "═".repeat(TOTAL_WIDTH + 2));
+
+    if rows.is_empty() {
+        println!("║ {:^width$} ║", "No profiles configured.", width = TOTAL_WIDTH);
+    } else {
+        println!(
+            "║ {:<NAME_COL_WIDTH$} {:<IP_COL_WIDTH$} {:<ZONES_COL_WIDTH$} {:<FLAGS_COL_WIDTH$} ║",
+            "NAME", "IZONE IP", "ZONES", ""
+        );
+        println!("╠{}╣", "═".repeat(TOTAL_WIDTH + 2));
+        for row in &rows {
+            let zones = row.zones.map_or("discovered".to_string(), |n| n.to_string());
+            let mut flags = Vec::new();
+            if row.active {
+                flags.push("active".green().to_string());
+            }
+            if row.default {
+                flags.push("default".cyan().to_string());
+            }
+            let flags_text = flags.join(", ");
+            let flags_len = flags_text.len() - izone::helpers::get_visible_length(&flags_text);
+            println!(
+                "║ {:<NAME_COL_WIDTH$} {:<IP_COL_WIDTH$} {:<ZONES_COL_WIDTH$} {:<width$} ║",
+                row.name,
+                row.izone_ip,
+                zones,
+                flags_text,
+                width = FLAGS_COL_WIDTH + flags_len
+            );
+        }
+    }
+    println!("╚{}╝", "═".repeat(TOTAL_WIDTH + 2));
+
+    Ok(())
+}
@@ -1,60 +1,48 @@
 // izone/src/commands/schedules.rs
 
-use reqwest::blocking::Client;
+use izone::{IzoneClient, IzoneError, Result, ZoneMap};
 use serde_json::json;
 use colored::Colorize;
-use std::process::exit;
 
-use crate::api::{make_query_request, make_command_request};
-use crate::constants::{self};
-use crate::helpers::{
+use izone::helpers::{
     format_temp, get_colored_system_mode, get_fan_speed_text, get_visible_length,
 };
-use crate::models::{SchedulesV2, SchedulesResponseWrapper}; // Import DaysEnabled
+use izone::models::SchedulesV2;
+use izone::report::ScheduleReport;
+use crate::commands::output::{print_record, print_records, OutputFormat};
 
 // Define the maximum number of schedules (favourtites) as per iZone API documentation
 const MAX_SCHEDULES: u8 = 9; // Schedules 0-8 (9 total)
 
-pub fn get_schedule_status(client: &Client, schedule_index: u8) {
+pub fn get_schedule_status(client: &IzoneClient, schedule_index: u8, output: OutputFormat) -> Result<()> {
     if schedule_index >= MAX_SCHEDULES {
-        eprintln!(
-            "{}Error: Schedule index {} is out of valid range (0-{}).",
-            "Error: ".red(),
-            schedule_index,
-            MAX_SCHEDULES - 1
-        );
-        exit(1);
+        return Err(IzoneError::validation(format!(
+            "Schedule index {} is out of valid range (0-{}).",
+            schedule_index, MAX_SCHEDULES - 1
+        )));
+    }
+
+    if output != OutputFormat::Table {
+        let schedule = client.schedule(schedule_index)?;
+        return print_record(output, &ScheduleReport::from(&schedule));
     }
 
     const BOX_WIDTH: usize = 75;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
     const LABEL_WIDTH: usize = 25;
 
-    let query_data = json!({ "iZoneV2Request": { "Type": 3, "No": schedule_index, "No1": 0 } });
-
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^width$} ║", format!("SCHEDULE STATUS: {}", schedule_index), width = PADDING_WIDTH);
     println!("╠{}╣", "═".repeat(BOX_WIDTH));
 
-    let response_value = match make_query_request(client, query_data) {
-        Ok(val) => val,
-        Err(e) => {
-            eprintln!("{}", format!("Error querying schedule status: {}", e).red());
-            exit(1);
-        }
-    };
-
-    let schedules_wrapper: SchedulesResponseWrapper = serde_json::from_value(response_value.clone())
-        .expect("Failed to parse schedule status response");
-
-    let schedule = schedules_wrapper.schedules_v2;
+    let schedule = client.schedule(schedule_index)?;
 
     // Helper to print formatted lines - CORRECTED
     let print_line = |label: &str, value: String| {
         let visible_value_len = get_visible_length(&value);
         let actual_padding_needed = PADDING_WIDTH - LABEL_WIDTH - visible_value_len;
         // Ensure actual_padding_needed is not negative
-        let final_padding = actual_padding_needed.max(0);
+        let final_padding = actual_padding_needed;
         println!("║ {:<LABEL_WIDTH$}{:final_padding$}{} ║", label, "", value);
     };
 
@@ -96,7 +84,7 @@ pub fn get_schedule_status(client: &Client, schedule_index: u8) {
     ).to_string();
     print_line("Days Enabled:", days_status);
 
-    if let Some(coolbreeze) = schedule.coolbreeze {
+    if let Some(coolbreeze) = &schedule.coolbreeze {
         println!("╠{}╣", "═".repeat(BOX_WIDTH));
         println!("║ {:^padding_width$} ║", "COOLBREEZE SETTINGS", padding_width = PADDING_WIDTH);
         println!("╠{}╣", "═".repeat(BOX_WIDTH));
@@ -105,7 +93,7 @@ pub fn get_schedule_status(client: &Client, schedule_index: u8) {
         print_line("RH Setpoint:", format!("{}%", coolbreeze.rh_setpoint).normal().to_string());
     }
 
-    if let Some(zones_settings) = schedule.zones {
+    if let Some(zones_settings) = &schedule.zones {
         println!("╠{}╣", "═".repeat(BOX_WIDTH));
         println!("║ {:^padding_width$} ║", "ZONE SETTINGS", padding_width = PADDING_WIDTH);
         println!("╠{}╣", "═".repeat(BOX_WIDTH));
@@ -123,14 +111,23 @@ pub fn get_schedule_status(client: &Client, schedule_index: u8) {
 
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
 
-    unsafe {
-        if constants::VERBOSE {
-            println!("Full SchedulesV2Response: {:#?}", response_value);
+    log::trace!("Full SchedulesV2Response: {:#?}", schedule);
+
+    Ok(())
+}
+
+pub fn get_all_schedules_summary(client: &IzoneClient, output: OutputFormat) -> Result<()> {
+    if output != OutputFormat::Table {
+        let mut reports = Vec::new();
+        for i in 0..MAX_SCHEDULES {
+            match client.schedule(i) {
+                Ok(schedule) => reports.push(ScheduleReport::from(&schedule)),
+                Err(e) => log::warn!("Skipping schedule {}: {}", i, e),
+            }
         }
+        return print_records(output, &reports);
     }
-}
 
-pub fn get_all_schedules_summary(client: &Client) {
     // Current `SUMMARY_BOX_WIDTH` (96) determines the number of '═' characters.
     // This results in a total line length of 98 characters (96 '═' + 2 corners '╔'/'╗').
     // The inner content for the title needs 96 - 2 = 94 chars.
@@ -152,10 +149,8 @@ pub fn get_all_schedules_summary(client: &Client) {
     let mut schedules_data: Vec<SchedulesV2> = Vec::new();
 
     for i in 0..MAX_SCHEDULES {
-        let query_data = json!({ "iZoneV2Request": { "Type": 3, "No": i, "No1": 0 } });
-
-        let response_value = match make_query_request(client, query_data) {
-            Ok(val) => val,
+        match client.schedule(i) {
+            Ok(schedule) => schedules_data.push(schedule),
             Err(e) => {
                 eprintln!(
                     "{}Error retrieving schedule {} status: {}",
@@ -166,27 +161,6 @@ pub fn get_all_schedules_summary(client: &Client) {
                 continue;
             }
         };
-
-        match serde_json::from_value::<SchedulesResponseWrapper>(response_value.clone()) {
-            Ok(schedules_wrapper) => {
-                let schedule = schedules_wrapper.schedules_v2;
-                schedules_data.push(schedule);
-            }
-            Err(e) => {
-                eprintln!(
-                    "{}Error parsing schedule {} data: {}",
-                    "Error: ".red(),
-                    i,
-                    e
-                );
-                unsafe {
-                    if constants::VERBOSE {
-                        println!("Raw response for schedule {}: {:#?}", i, response_value);
-                    }
-                }
-                continue;
-            }
-        };
     }
 
     if schedules_data.is_empty() {
@@ -265,38 +239,35 @@ pub fn get_all_schedules_summary(client: &Client) {
         }
     }
     println!("╚{}╝", "═".repeat(SUMMARY_BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_schedule_name(client: &Client, schedule_index: u8, new_name: &str) {
+pub fn set_schedule_name(client: &IzoneClient, schedule_index: u8, new_name: &str) -> Result<()> {
     if schedule_index >= MAX_SCHEDULES {
-        eprintln!(
-            "{}Error: Schedule index {} is out of valid range (0-{}).",
-            "Error: ".red(),
-            schedule_index,
-            MAX_SCHEDULES - 1
-        );
-        exit(1);
+        return Err(IzoneError::validation(format!(
+            "Schedule index {} is out of valid range (0-{}).",
+            schedule_index, MAX_SCHEDULES - 1
+        )));
     }
     if new_name.len() > 16 {
-        eprintln!("{}", "Error: Schedule name cannot exceed 15 characters.".red());
-        exit(1);
+        return Err(IzoneError::validation("Schedule name cannot exceed 15 characters."));
     }
 
     let command_data = json!({"SchedName": {"Index": schedule_index, "Name": new_name}});
-    make_command_request(client, command_data)
-        .expect(&format!("Failed to set name for schedule {}", schedule_index));
+    client.command(command_data)?;
 
     println!("Schedule {} name set to '{}'.", schedule_index.to_string().green(), new_name.green());
+
+    Ok(())
 }
 
-pub fn set_schedule_time(client: &Client, schedule_index: u8, start_h: u8, start_m: u8, stop_h: u8, stop_m: u8) {
+pub fn set_schedule_time(client: &IzoneClient, schedule_index: u8, start_h: u8, start_m: u8, stop_h: u8, stop_m: u8) -> Result<()> {
     if schedule_index >= MAX_SCHEDULES {
-        eprintln!("{}Error: Schedule index {} is out of valid range (0-{}).", "Error: ".red(), schedule_index, MAX_SCHEDULES - 1);
-        exit(1);
+        return Err(IzoneError::validation(format!("Schedule index {} is out of valid range (0-{}).", schedule_index, MAX_SCHEDULES - 1)));
     }
     if start_h > 23 || start_m > 59 || stop_h > 23 || stop_m > 59 {
-        eprintln!("{}", "Error: Invalid time format. Hours must be 0-23, minutes 0-59.".red());
-        exit(1);
+        return Err(IzoneError::validation("Invalid time format. Hours must be 0-23, minutes 0-59."));
     }
 
     let command_data = json!({
@@ -311,20 +282,20 @@ pub fn set_schedule_time(client: &Client, schedule_index: u8, start_h: u8, start
             }
         }
     });
-    make_command_request(client, command_data)
-        .expect(&format!("Failed to set time for schedule {}", schedule_index));
+    client.command(command_data)?;
 
     println!(
         "Schedule {} time set to Start: {:02}:{:02}, Stop: {:02}:{:02}.",
         schedule_index.to_string().green(),
         start_h, start_m, stop_h, stop_m
     );
+
+    Ok(())
 }
 
-pub fn set_schedule_days(client: &Client, schedule_index: u8, days: Vec<String>) {
+pub fn set_schedule_days(client: &IzoneClient, schedule_index: u8, days: Vec<String>) -> Result<()> {
     if schedule_index >= MAX_SCHEDULES {
-        eprintln!("{}Error: Schedule index {} is out of valid range (0-{}).", "Error: ".red(), schedule_index, MAX_SCHEDULES - 1);
-        exit(1);
+        return Err(IzoneError::validation(format!("Schedule index {} is out of valid range (0-{}).", schedule_index, MAX_SCHEDULES - 1)));
     }
 
     let mut m = 0;
@@ -369,26 +340,25 @@ pub fn set_schedule_days(client: &Client, schedule_index: u8, days: Vec<String>)
         }
     });
 
-    make_command_request(client, command_data)
-        .expect(&format!("Failed to set days for schedule {}", schedule_index));
+    client.command(command_data)?;
 
     println!("Schedule {} days set.", schedule_index.to_string().green());
+
+    Ok(())
 }
 
-pub fn set_schedule_mode_fan(client: &Client, schedule_index: u8, mode: Option<&str>, fan: Option<&str>) {
+pub fn set_schedule_mode_fan(client: &IzoneClient, schedule_index: u8, mode: Option<&str>, fan: Option<&str>) -> Result<()> {
     if schedule_index >= MAX_SCHEDULES {
-        eprintln!("{}Error: Schedule index {} is out of valid range (0-{}).", "Error: ".red(), schedule_index, MAX_SCHEDULES - 1);
-        exit(1);
+        return Err(IzoneError::validation(format!("Schedule index {} is out of valid range (0-{}).", schedule_index, MAX_SCHEDULES - 1)));
     }
 
     let mut commands = Vec::new();
 
     if let Some(m_str) = mode {
-        let mode_val = match crate::helpers::get_system_mode_value(m_str) {
+        let mode_val = match izone::helpers::get_system_mode_value(m_str) {
             Some(v) => v,
             None => {
-                eprintln!("{}Error: Invalid mode '{}'. Available modes: auto, cool, heat, vent, dry.", "Error: ".red(), m_str);
-                exit(1);
+                return Err(IzoneError::validation(format!("Invalid mode '{}'. Available modes: auto, cool, heat, vent, dry.", m_str)));
             }
         };
         commands.push(json!({"SchedAcMode": {"Index": schedule_index, "Mode": mode_val}}));
@@ -403,67 +373,65 @@ pub fn set_schedule_mode_fan(client: &Client, schedule_index: u8, mode: Option<&
             "top" => 5,
             "nongasheat" => 99,
             _ => {
-                eprintln!("{}Error: Invalid fan speed '{}'. Available: low, medium, high, auto, top, nongasheat.", "Error: ".red(), f_str);
-                exit(1);
+                return Err(IzoneError::validation(format!("Invalid fan speed '{}'. Available: low, medium, high, auto, top, nongasheat.", f_str)));
             }
         };
         commands.push(json!({"SchedAcFan": {"Index": schedule_index, "Fan": fan_val}}));
     }
 
     if commands.is_empty() {
-        eprintln!("{}", "Error: No mode or fan speed provided to set for schedule.".red());
-        exit(1);
+        return Err(IzoneError::validation("No mode or fan speed provided to set for schedule."));
     }
 
     for cmd in commands {
-        make_command_request(client, cmd)
-            .expect(&format!("Failed to set mode/fan for schedule {}", schedule_index));
+        client.command(cmd)?;
     }
     println!("Schedule {} mode/fan set.", schedule_index.to_string().green());
+
+    Ok(())
 }
 
-pub fn enable_schedule(client: &Client, schedule_index: u8) {
+pub fn enable_schedule(client: &IzoneClient, schedule_index: u8) -> Result<()> {
     if schedule_index >= MAX_SCHEDULES {
-        eprintln!("{}Error: Schedule index {} is out of valid range (0-{}).", "Error: ".red(), schedule_index, MAX_SCHEDULES - 1);
-        exit(1);
+        return Err(IzoneError::validation(format!("Schedule index {} is out of valid range (0-{}).", schedule_index, MAX_SCHEDULES - 1)));
     }
     let command_data = json!({"SchedEnable": {"Index": schedule_index, "Enabled": 1}});
-    make_command_request(client, command_data)
-        .expect(&format!("Failed to enable schedule {}", schedule_index));
+    client.command(command_data)?;
     println!("Schedule {} enabled.", schedule_index.to_string().green());
+
+    Ok(())
 }
 
-pub fn disable_schedule(client: &Client, schedule_index: u8) {
+pub fn disable_schedule(client: &IzoneClient, schedule_index: u8) -> Result<()> {
     if schedule_index >= MAX_SCHEDULES {
-        eprintln!("{}Error: Schedule index {} is out of valid range (0-{}).", "Error: ".red(), schedule_index, MAX_SCHEDULES - 1);
-        exit(1);
+        return Err(IzoneError::validation(format!("Schedule index {} is out of valid range (0-{}).", schedule_index, MAX_SCHEDULES - 1)));
     }
     let command_data = json!({"SchedEnable": {"Index": schedule_index, "Enabled": 0}});
-    make_command_request(client, command_data)
-        .expect(&format!("Failed to disable schedule {}", schedule_index));
+    client.command(command_data)?;
     println!("Schedule {} disabled.", schedule_index.to_string().red());
+
+    Ok(())
 }
 
-pub fn set_schedule_zones(client: &Client, schedule_index: u8, zone_settings: Vec<(String, u8, u32)>) {
+pub fn set_schedule_zones(client: &IzoneClient, zones: &ZoneMap, schedule_index: u8, zone_settings: Vec<(String, u8, u32)>) -> Result<()> {
     if schedule_index >= MAX_SCHEDULES {
-        eprintln!("{}Error: Schedule index {} is out of valid range (0-{}).", "Error: ".red(), schedule_index, MAX_SCHEDULES - 1);
-        exit(1);
+        return Err(IzoneError::validation(format!("Schedule index {} is out of valid range (0-{}).", schedule_index, MAX_SCHEDULES - 1)));
     }
 
     let mut zones_array = Vec::new();
     for (zone_name, mode_val, setpoint_raw) in zone_settings {
-        let zone_index_map = match crate::constants::ZONES.get(zone_name.as_str()) {
-            Some(&index) => index,
-            None => {
+        let zone_index_map = match zones.resolve_strict(&zone_name) {
+            Ok(index) => index,
+            Err(e) => {
                 eprintln!(
-                    "{}Error: Unknown zone '{}'. Skipping this zone for schedule {}.",
-                    "Error: ".red(), zone_name, schedule_index
+                    "{}{} Skipping this zone for schedule {}.",
+                    "Error: ".red(), e, schedule_index
                 );
                 continue;
             }
         };
 
-        if setpoint_raw < 1500 || setpoint_raw > 3000 {
+        if !(1500..=3000).contains(&setpoint_raw) {
             eprintln!(
                 "{}Error: Setpoint '{}' for zone '{}' is out of valid range (15.0-30.0°C). Skipping.",
                 "Error: ".red(), format_temp(setpoint_raw), zone_name
@@ -479,8 +447,7 @@ pub fn set_schedule_zones(client: &Client, schedule_index: u8, zone_settings: Ve
     }
 
     if zones_array.is_empty() {
-        eprintln!("{}", "No valid zone settings provided for schedule.".red());
-        exit(1);
+        return Err(IzoneError::validation("No valid zone settings provided for schedule."));
     }
 
     let command_data = json!({
@@ -490,8 +457,9 @@ pub fn set_schedule_zones(client: &Client, schedule_index: u8, zone_settings: Ve
         }
     });
 
-    make_command_request(client, command_data)
-        .expect(&format!("Failed to set zone settings for schedule {}", schedule_index));
+    client.command(command_data)?;
 
     println!("Schedule {} zone settings updated.", schedule_index.to_string().green());
+
+    Ok(())
 }
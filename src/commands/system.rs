@@ -1,19 +1,25 @@
 // izone/src/commands/system.rs
 
-use reqwest::blocking::Client;
+use izone::{IzoneClient, IzoneError, Result};
 use serde_json::json;
 use colored::Colorize;
-use std::process::exit;
 use stringcase::Caser;
-// Corrected imports for API functions and get_visible_length from helpers
-use crate::api::{make_query_request, make_command_request};
-use crate::constants;
-use crate::helpers::{format_temp, get_colored_system_mode, get_fan_speed_text, get_visible_length, get_system_mode_value, get_fan_speed_value};
-use crate::models::SystemV2Response;
+use izone::api;
+use izone::report::{FanAutoReport, LinkageReport, PanelReport, SystemReport};
+use crate::commands::output::{print_record, OutputFormat};
+use izone::helpers::{format_temp, get_colored_system_mode, get_fan_speed_text, get_visible_length, get_system_mode_value, get_fan_speed_value, get_ras_text, get_ras_value, get_zone_type_text, get_linkage_key, get_panel_key, get_allowed_fan_speeds, get_fan_auto_type_text, get_fan_auto_type_value, CTRL_ZONE_UNIT_SETPOINT};
+use izone::ZoneMap;
+use izone::constants::MAX_ZONES;
+use izone::models::{SystemV2, ZonesV2};
 
 // Removed: The `print_status_line` helper function has been removed as requested.
 
-pub fn get_system_status(client: &Client) {
+pub fn get_system_status(client: &IzoneClient, output: OutputFormat) -> Result<()> {
+    if output != OutputFormat::Table {
+        let sys_v2 = client.system()?;
+        return print_record(output, &SystemReport::from(&sys_v2));
+    }
+
     const BOX_WIDTH: usize = 45; // Total width of the box's horizontal lines
     const PADDING_WIDTH: usize = BOX_WIDTH - 2; // Subtract 2 for the '║ ' and ' ║'
     const LABEL_WIDTH: usize = 24; // Width for the labels like "Aircon Power:", "Mode:", etc.
@@ -22,33 +28,7 @@ pub fn get_system_status(client: &Client) {
     println!("║ {:^padding_width$} ║", "AIRCON STATUS", padding_width = PADDING_WIDTH);
     println!("╠{}╣", "═".repeat(BOX_WIDTH));
 
-    let query_data = json!({ "iZoneV2Request": { "Type": 1, "No": 0, "No1": 0 } });
-
-    let response_value = match make_query_request(client, query_data) {
-        Ok(val) => val,
-        Err(e) => {
-            eprintln!("{}", e.red());
-            exit(1);
-        }
-    };
-
-    let system_response: SystemV2Response = match serde_json::from_value(response_value.clone()) {
-        Ok(resp) => resp,
-        Err(e) => {
-            eprintln!(
-                "{}",
-                format!(
-                    "Unexpected response from iZone controller at {}.\n\
-                    Ensure your configuration has the correct iZone IP and the controller is reachable in your network.\n\
-                    Error details: Failed to parse system status response - {}",
-                    &**constants::IZONE_IP, e
-                ).red()
-            );
-            exit(1);
-        }
-    };
-
-    let sys_v2 = system_response.system_v2;
+    let sys_v2 = client.system()?;
 
     let status_text = if sys_v2.sys_on {
         "ON".green().to_string()
@@ -69,6 +49,17 @@ pub fn get_system_status(client: &Client) {
     let sys_setpoint_line = format!("{:width$} {}°C", "Target Setpoint:", format_temp(sys_v2.setpoint), width = LABEL_WIDTH);
     let sys_temp_line = format!("{:width$} {}°C", "Controller Temperature:", format_temp(sys_v2.temp).cyan(), width = LABEL_WIDTH);
     let ac_error_line = format!("{:width$} {}", "System Check Status:", ac_error_text, width = LABEL_WIDTH - 1); // Adjusted width for System Check Status
+    let control_source_line = format!("{:width$} {}", "Control Source:", get_control_source_text(sys_v2.ras).cyan(), width = LABEL_WIDTH);
+    let lock_text = if sys_v2.lock_status == 1 {
+        "Locked".red().to_string()
+    } else if sys_v2.lock_on == 1 {
+        "Timer on".yellow().to_string()
+    } else {
+        "Off".normal().to_string()
+    };
+    let lock_line = format!("{:width$} {}", "System Lock:", lock_text, width = LABEL_WIDTH);
+    let isave_line = format!("{:width$} {}", "iSave:", get_isave_text(&sys_v2), width = LABEL_WIDTH);
+    let control_zone_line = format!("{:width$} {}", "Control Zone:", get_control_zone_text(client, sys_v2.ctrl_zone, PADDING_WIDTH - LABEL_WIDTH - 1).cyan(), width = LABEL_WIDTH);
 
     // Print each line, adjusting the external padding based on the visible length of the formatted line
     println!("║ {:<pw$} ║", sys_on_line, pw = PADDING_WIDTH - get_visible_length(&sys_on_line) + sys_on_line.len());
@@ -78,17 +69,47 @@ pub fn get_system_status(client: &Client) {
     println!("║ {:<pw$} ║", sys_setpoint_line, pw = PADDING_WIDTH - get_visible_length(&sys_setpoint_line) + sys_setpoint_line.len() - 1);
     println!("║ {:<pw$} ║", sys_temp_line, pw = PADDING_WIDTH - get_visible_length(&sys_temp_line) + sys_temp_line.len() - 1);
     println!("║ {:<pw$} ║", ac_error_line, pw = PADDING_WIDTH - get_visible_length(&ac_error_line) + ac_error_line.len());
+    println!("║ {:<pw$} ║", control_source_line, pw = PADDING_WIDTH.saturating_sub(get_visible_length(&control_source_line)) + control_source_line.len());
+    println!("║ {:<pw$} ║", control_zone_line, pw = PADDING_WIDTH.saturating_sub(get_visible_length(&control_zone_line)) + control_zone_line.len());
+    println!("║ {:<pw$} ║", lock_line, pw = PADDING_WIDTH.saturating_sub(get_visible_length(&lock_line)) + lock_line.len());
+    println!("║ {:<pw$} ║", isave_line, pw = PADDING_WIDTH.saturating_sub(get_visible_length(&isave_line)) + isave_line.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
 
-    unsafe {
-        if constants::VERBOSE {
-            // Use the original response_value for verbose output
-            println!("Full SystemV2Response: {:#?}", response_value);
+    log::trace!("Full SystemV2Response: {:#?}", sys_v2);
+
+    Ok(())
+}
+
+/// Describes ReturnAirSensor_e for the status view.
+fn get_control_source_text(ras: u8) -> String {
+    match ras {
+        1 => "Return air (RAS)".to_string(),
+        2 => "Master zone".to_string(),
+        3 => "Zones".to_string(),
+        _ => get_ras_text(ras),
+    }
+}
+
+/// Names the zone controlling the unit, or "Unit setpoint" for `CtrlZone` 15.
+/// Zone names (up to 15 characters) are shortened to fit in `width`.
+fn get_control_zone_text(client: &IzoneClient, ctrl_zone: u8, width: usize) -> String {
+    if ctrl_zone == CTRL_ZONE_UNIT_SETPOINT {
+        return "Unit setpoint".to_string();
+    }
+    match client.zone(ctrl_zone) {
+        Ok(zone) => {
+            let index = format!(" ({})", ctrl_zone);
+            let name: String = zone.name.chars().take(width.saturating_sub(index.len())).collect();
+            format!("{}{}", name.trim_end(), index)
+        }
+        Err(e) => {
+            log::warn!("Could not read control zone {}: {}", ctrl_zone, e);
+            format!("Zone {}", ctrl_zone)
         }
     }
 }
 
-pub fn get_system_temperature(client: &Client) {
+pub fn get_system_temperature(client: &IzoneClient) -> Result<()> {
     const BOX_WIDTH: usize = 45;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
@@ -96,51 +117,23 @@ pub fn get_system_temperature(client: &Client) {
     println!("║ {:^padding_width$} ║", "Controller Temperature", padding_width = PADDING_WIDTH);
     println!("╠{}╣", "═".repeat(BOX_WIDTH));
 
-    let query_data = json!({ "iZoneV2Request": { "Type": 1, "No": 0, "No1": 0 } });
+    let sys_v2 = client.system()?;
 
-    let response_value = match make_query_request(client, query_data) {
-        Ok(val) => val,
-        Err(e) => {
-            eprintln!("{}", e.red());
-            exit(1);
-        }
-    };
-
-    let system_response: SystemV2Response = match serde_json::from_value(response_value.clone()) {
-        Ok(resp) => resp,
-        Err(e) => {
-            eprintln!(
-                "{}",
-                format!(
-                    "Unexpected response from iZone controller at {}.\n\
-                    Ensure your configuration has the correct iZone IP and the controller is reachable in your network.\n\
-                    Error details: Failed to parse controller temperature response - {}",
-                    &**constants::IZONE_IP, e
-                ).red()
-            );
-            exit(1);
-        }
-    };
-
-    let temp_text = format_temp(system_response.system_v2.temp);
+    let temp_text = format_temp(sys_v2.temp);
     let temp_line = format!("Current Controller Temperature: {}°C", temp_text.cyan());
     println!("║ {:<padding_width$} ║", temp_line, padding_width = PADDING_WIDTH -1 - get_visible_length(&temp_line) + temp_line.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
 
-    unsafe {
-        if constants::VERBOSE {
-            println!("{}", serde_json::to_string_pretty(&response_value).unwrap());
-        }
-    }
+    log::trace!("{}", serde_json::to_string_pretty(&sys_v2).unwrap_or_default());
+
+    Ok(())
 }
 
-pub fn turn_on_ac(client: &Client) {
+pub fn turn_on_ac(client: &IzoneClient) -> Result<()> {
     const BOX_WIDTH: usize = 45;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
-    let command_data = json!({"SysOn":1});
-    make_command_request(client, command_data)
-        .expect("Failed to turn on AC system");
+    client.set_system_on(true)?;
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "System Control", padding_width = PADDING_WIDTH);
     println!("╠{}╣", "═".repeat(BOX_WIDTH));
@@ -148,15 +141,146 @@ pub fn turn_on_ac(client: &Client) {
     let message = format!("AC system turned {}.", "ON".green());
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
+}
+
+/// Checks a lock keycode: 1 to 6 digits.
+fn validate_lock_code(code: &str) -> Result<()> {
+    if code.is_empty() || code.len() > 6 || !code.chars().all(|c| c.is_ascii_digit()) {
+        return Err(IzoneError::validation("The keycode must be 1 to 6 digits."));
+    }
+    Ok(())
+}
+
+/// Starts the lock timer: the system locks after `days` and then only accepts
+/// the keycode. Without `code`, the current keycode is kept.
+pub fn lock_system(client: &IzoneClient, days: u16, code: Option<&str>) -> Result<()> {
+    if !(1..=365).contains(&days) {
+        return Err(IzoneError::validation("Lock days must be 1-365"));
+    }
+
+    let code = match code {
+        Some(code) => code.to_string(),
+        None => {
+            let current = client.system()?.lock_code;
+            if current.is_empty() {
+                return Err(IzoneError::validation("No keycode is set on the controller; pass one with --code."));
+            }
+            current
+        }
+    };
+    validate_lock_code(&code)?;
+
+    client.command(api::lock_system_command(true, &code, days))?;
+    if client.system()?.lock_on != 1 {
+        return Err(IzoneError::Controller {
+            url: client.base_url().to_string(),
+            message: "LockOn is still 0 after LockSystem; the lock timer was not started.".to_string(),
+        });
+    }
+
+    const BOX_WIDTH: usize = 50;
+    const PADDING_WIDTH: usize = BOX_WIDTH - 2;
+
+    println!("╔{}╗", "═".repeat(BOX_WIDTH));
+    println!("║ {:^padding_width$} ║", "System Lock", padding_width = PADDING_WIDTH);
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+    let message = format!("System will lock in {} day{}.", days.to_string().yellow(), if days == 1 { "" } else { "s" });
+    println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
+    println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
+}
+
+/// Unlocks the system and stops the lock timer with the keycode.
+pub fn unlock_system(client: &IzoneClient, code: &str) -> Result<()> {
+    validate_lock_code(code)?;
+
+    client.command(api::lock_system_command(false, code, 0))?;
+    let after = client.system()?;
+    if after.lock_status == 1 || after.lock_on == 1 {
+        return Err(IzoneError::Controller {
+            url: client.base_url().to_string(),
+            message: "The system is still locked; check the keycode.".to_string(),
+        });
+    }
+
+    const BOX_WIDTH: usize = 50;
+    const PADDING_WIDTH: usize = BOX_WIDTH - 2;
+
+    println!("╔{}╗", "═".repeat(BOX_WIDTH));
+    println!("║ {:^padding_width$} ║", "System Lock", padding_width = PADDING_WIDTH);
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+    let message = format!("System {}.", "unlocked".green());
+    println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
+    println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
+}
+
+/// Describes `iSaveOn`, or that the iSave option is not enabled.
+fn get_isave_text(sys_v2: &SystemV2) -> String {
+    if sys_v2.isave_enable == 0 {
+        "Not enabled".dimmed().to_string()
+    } else if sys_v2.isave_on == 1 {
+        "On".green().to_string()
+    } else {
+        "Off".normal().to_string()
+    }
+}
+
+/// Reads the system and refuses when the iSave option is not enabled.
+fn isave_system(client: &IzoneClient) -> Result<SystemV2> {
+    let sys_v2 = client.system()?;
+    if sys_v2.isave_enable == 0 {
+        return Err(IzoneError::validation(
+            "iSave is not enabled on this system (iSaveEnable = 0).\nThe iSave option has to be enabled by the installer on the iZone controller first.",
+        ));
+    }
+    Ok(sys_v2)
+}
+
+fn print_isave(message: &str) {
+    const BOX_WIDTH: usize = 45;
+    const PADDING_WIDTH: usize = BOX_WIDTH - 2;
+
+    println!("╔{}╗", "═".repeat(BOX_WIDTH));
+    println!("║ {:^padding_width$} ║", "iSave", padding_width = PADDING_WIDTH);
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+    println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(message) + message.len());
+    println!("╚{}╝", "═".repeat(BOX_WIDTH));
+}
+
+/// Shows whether iSave is on.
+pub fn get_isave_status(client: &IzoneClient) -> Result<()> {
+    let sys_v2 = isave_system(client)?;
+    print_isave(&format!("iSave is {}.", get_isave_text(&sys_v2)));
+    Ok(())
+}
+
+/// Turns iSave on or off (`iSaveOn`) and reads it back.
+pub fn set_isave(client: &IzoneClient, on: bool) -> Result<()> {
+    isave_system(client)?;
+
+    client.command(api::isave_on_command(on))?;
+    let sys_v2 = client.system()?;
+    if sys_v2.isave_on != on as u8 {
+        return Err(IzoneError::Controller {
+            url: client.base_url().to_string(),
+            message: format!("iSaveOn still reports {} after setting it to {}.", sys_v2.isave_on, on as u8),
+        });
+    }
+
+    print_isave(&format!("iSave turned {}.", if on { "ON".green() } else { "OFF".red() }));
+    Ok(())
 }
 
-pub fn turn_off_ac(client: &Client) {
+pub fn turn_off_ac(client: &IzoneClient) -> Result<()> {
     const BOX_WIDTH: usize = 45;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
-    let command_data = json!({"SysOn":0});
-    make_command_request(client, command_data)
-        .expect("Failed to turn off AC system");
+    client.set_system_on(false)?;
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "System Control", padding_width = PADDING_WIDTH);
     println!("╠{}╣", "═".repeat(BOX_WIDTH));
@@ -164,28 +288,22 @@ pub fn turn_off_ac(client: &Client) {
     let message = format!("AC system turned {}.", "OFF".red());
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_system_mode(client: &Client, mode_name: &str) {
+pub fn set_system_mode(client: &IzoneClient, mode_name: &str) -> Result<()> {
     const BOX_WIDTH: usize = 45;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
-    let mode_value = match get_system_mode_value(mode_name) {
-        Some(value) => value,
-        None => {
-            // Construct the error message clearly to avoid character literal issues
-            let error_message = format!(
-                "Error: Unknown system mode '{}'.\nAvailable modes: auto, cool, heat, vent, dry.", // Updated modes list
-                mode_name
-            ).red().to_string();
-            eprintln!("{}", error_message);
-            exit(1);
-        }
-    };
+    let mode_value = get_system_mode_value(mode_name).ok_or_else(|| {
+        IzoneError::validation(format!(
+            "Unknown system mode '{}'.\nAvailable modes: auto, cool, heat, vent, dry.",
+            mode_name
+        ))
+    })?;
 
-    let command_data = json!({"SysMode": mode_value}); // Use SysMode command
-    make_command_request(client, command_data)
-        .expect(&format!("Failed to set system mode to '{}'", mode_name));
+    client.set_system_mode(mode_value)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "System Control", padding_width = PADDING_WIDTH);
@@ -194,27 +312,39 @@ pub fn set_system_mode(client: &Client, mode_name: &str) {
     let message = format!("System mode set to {}.", mode_name.to_pascal_case().cyan());
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_system_fan(client: &Client, fan_speed_name: &str) {
+pub fn set_system_fan(client: &IzoneClient, fan_speed_name: &str) -> Result<()> {
     const BOX_WIDTH: usize = 45;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
-    let fan_speed_value = match get_fan_speed_value(fan_speed_name) {
-        Some(value) => value,
-        None => {
-            let error_message = format!(
-                "Error: Unknown fan speed '{}'.\nAvailable fan speeds: auto, low, medium, high.",
-                fan_speed_name
-            ).red().to_string();
-            eprintln!("{}", error_message);
-            exit(1);
-        }
-    };
+    let fan_speed_value = get_fan_speed_value(fan_speed_name).ok_or_else(|| {
+        IzoneError::validation(format!(
+            "Unknown fan speed '{}'.\nAvailable fan speeds: auto, low, medium, high, top.",
+            fan_speed_name
+        ))
+    })?;
+
+    // The unit only has the speeds its fan auto settings allow.
+    let sys_v2 = client.system()?;
+    let allowed = get_allowed_fan_speeds(sys_v2.fan_auto_en, sys_v2.fan_auto_type);
+    if !allowed.contains(&fan_speed_value) {
+        let reason = if sys_v2.fan_auto_en == Some(0) {
+            "fan auto is disabled on this system".to_string()
+        } else {
+            format!("the AC unit fan is configured as {}", get_fan_auto_type_text(sys_v2.fan_auto_type).to_lowercase())
+        };
+        return Err(IzoneError::validation(format!(
+            "Fan speed {} is not available: {}.\nAvailable fan speeds: {}.\nSee 'izone config fan-auto' to change the fan settings.",
+            get_fan_speed_text(fan_speed_value),
+            reason,
+            fan_speed_list(allowed)
+        )));
+    }
 
-    let command_data = json!({"SysFan": fan_speed_value}); // Use SysFan command
-    make_command_request(client, command_data)
-        .expect(&format!("Failed to set system fan to '{}'", fan_speed_name));
+    client.set_system_fan(fan_speed_value)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "System Control", padding_width = PADDING_WIDTH);
@@ -223,22 +353,22 @@ pub fn set_system_fan(client: &Client, fan_speed_name: &str) {
     let message = format!("Aircon Fan Speed set to {}.", fan_speed_name.to_pascal_case().cyan());
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
 // ==================== COOLBREEZE COMMANDS ====================
 
-pub fn set_coolbreeze_fan_speed(client: &Client, speed: u8) {
-    if speed < 1 || speed > 100 {
-        eprintln!("{}", "Error: Fan speed must be 1-100%".red());
-        exit(1);
+pub fn set_coolbreeze_fan_speed(client: &IzoneClient, speed: u8) -> Result<()> {
+    if !(1..=100).contains(&speed) {
+        return Err(IzoneError::validation("Fan speed must be 1-100%"));
     }
 
     const BOX_WIDTH: usize = 45;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let command_data = json!({"CoolbreezeFanSpeed": speed});
-    make_command_request(client, command_data)
-        .expect("Failed to set Coolbreeze fan speed");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Coolbreeze Control", padding_width = PADDING_WIDTH);
@@ -246,20 +376,20 @@ pub fn set_coolbreeze_fan_speed(client: &Client, speed: u8) {
     let message = format!("Coolbreeze fan speed set to {}%.", speed.to_string().green());
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_coolbreeze_rh_setpoint(client: &Client, rh: u8) {
-    if rh < 10 || rh > 90 {
-        eprintln!("{}", "Error: RH setpoint must be 10-90%".red());
-        exit(1);
+pub fn set_coolbreeze_rh_setpoint(client: &IzoneClient, rh: u8) -> Result<()> {
+    if !(10..=90).contains(&rh) {
+        return Err(IzoneError::validation("RH setpoint must be 10-90%"));
     }
 
     const BOX_WIDTH: usize = 45;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let command_data = json!({"CoolbreezeRhSetpoint": rh});
-    make_command_request(client, command_data)
-        .expect("Failed to set Coolbreeze RH setpoint");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Coolbreeze Control", padding_width = PADDING_WIDTH);
@@ -267,25 +397,24 @@ pub fn set_coolbreeze_rh_setpoint(client: &Client, rh: u8) {
     let message = format!("Coolbreeze RH setpoint set to {}%.", rh.to_string().green());
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_coolbreeze_prewash(client: &Client, enable: bool, time_minutes: Option<u8>) {
+pub fn set_coolbreeze_prewash(client: &IzoneClient, enable: bool, time_minutes: Option<u8>) -> Result<()> {
     const BOX_WIDTH: usize = 45;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let enable_val = if enable { 1 } else { 0 };
     let command_data = json!({"CoolbreezePrewEn": enable_val});
-    make_command_request(client, command_data.clone())
-        .expect("Failed to set Coolbreeze prewash enable");
+    client.command(command_data.clone())?;
 
     if let Some(time) = time_minutes {
-        if time < 1 || time > 60 {
-            eprintln!("{}", "Error: Prewash time must be 1-60 minutes".red());
-            exit(1);
+        if !(1..=60).contains(&time) {
+            return Err(IzoneError::validation("Prewash time must be 1-60 minutes"));
         }
         let time_cmd = json!({"CoolbreezePrewTime": time});
-        make_command_request(client, time_cmd)
-            .expect("Failed to set Coolbreeze prewash time");
+        client.command(time_cmd)?;
     }
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
@@ -299,16 +428,17 @@ pub fn set_coolbreeze_prewash(client: &Client, enable: bool, time_minutes: Optio
     };
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_coolbreeze_drain_after_prewash(client: &Client, enable: bool) {
+pub fn set_coolbreeze_drain_after_prewash(client: &IzoneClient, enable: bool) -> Result<()> {
     const BOX_WIDTH: usize = 45;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let enable_val = if enable { 1 } else { 0 };
     let command_data = json!({"CoolbreezeDrAfPrewEn": enable_val});
-    make_command_request(client, command_data)
-        .expect("Failed to set Coolbreeze drain after prewash");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Coolbreeze Control", padding_width = PADDING_WIDTH);
@@ -317,26 +447,25 @@ pub fn set_coolbreeze_drain_after_prewash(client: &Client, enable: bool) {
     let message = format!("Drain after prewash {}.", status);
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_coolbreeze_drain_cycle(client: &Client, enable: bool, period_hours: Option<u16>) {
+pub fn set_coolbreeze_drain_cycle(client: &IzoneClient, enable: bool, period_hours: Option<u16>) -> Result<()> {
     const BOX_WIDTH: usize = 55;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let enable_val = if enable { 1 } else { 0 };
     let command_data = json!({"CoolbreezeDrCycEn": enable_val});
-    make_command_request(client, command_data)
-        .expect("Failed to set Coolbreeze drain cycle enable");
+    client.command(command_data)?;
 
     if let Some(hours) = period_hours {
-        if hours < 1 || hours > 50 {
-            eprintln!("{}", "Error: Drain cycle period must be 1-50 hours".red());
-            exit(1);
+        if !(1..=50).contains(&hours) {
+            return Err(IzoneError::validation("Drain cycle period must be 1-50 hours"));
         }
         let minutes = hours * 60;
         let period_cmd = json!({"CoolbreezeDrCycPer": minutes});
-        make_command_request(client, period_cmd)
-            .expect("Failed to set Coolbreeze drain cycle period");
+        client.command(period_cmd)?;
     }
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
@@ -350,25 +479,24 @@ pub fn set_coolbreeze_drain_cycle(client: &Client, enable: bool, period_hours: O
     };
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_coolbreeze_postwash(client: &Client, enable: bool, time_minutes: Option<u8>) {
+pub fn set_coolbreeze_postwash(client: &IzoneClient, enable: bool, time_minutes: Option<u8>) -> Result<()> {
     const BOX_WIDTH: usize = 45;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let enable_val = if enable { 1 } else { 0 };
     let command_data = json!({"CoolbreezePostwEn": enable_val});
-    make_command_request(client, command_data)
-        .expect("Failed to set Coolbreeze postwash enable");
+    client.command(command_data)?;
 
     if let Some(time) = time_minutes {
-        if time < 5 || time > 30 {
-            eprintln!("{}", "Error: Postwash time must be 5-30 minutes".red());
-            exit(1);
+        if !(5..=30).contains(&time) {
+            return Err(IzoneError::validation("Postwash time must be 5-30 minutes"));
         }
         let time_cmd = json!({"CoolbreezePostwT": time});
-        make_command_request(client, time_cmd)
-            .expect("Failed to set Coolbreeze postwash time");
+        client.command(time_cmd)?;
     }
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
@@ -382,16 +510,17 @@ pub fn set_coolbreeze_postwash(client: &Client, enable: bool, time_minutes: Opti
     };
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_coolbreeze_drain_before_postwash(client: &Client, enable: bool) {
+pub fn set_coolbreeze_drain_before_postwash(client: &IzoneClient, enable: bool) -> Result<()> {
     const BOX_WIDTH: usize = 45;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let enable_val = if enable { 1 } else { 0 };
     let command_data = json!({"CoolbreezeDrBfPostwEn": enable_val});
-    make_command_request(client, command_data)
-        .expect("Failed to set Coolbreeze drain before postwash");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Coolbreeze Control", padding_width = PADDING_WIDTH);
@@ -400,16 +529,17 @@ pub fn set_coolbreeze_drain_before_postwash(client: &Client, enable: bool) {
     let message = format!("Drain before postwash {}.", status);
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_coolbreeze_inverter(client: &Client, enable: bool) {
+pub fn set_coolbreeze_inverter(client: &IzoneClient, enable: bool) -> Result<()> {
     const BOX_WIDTH: usize = 45;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let enable_val = if enable { 1 } else { 0 };
     let command_data = json!({"CoolbreezeInverter": enable_val});
-    make_command_request(client, command_data)
-        .expect("Failed to set Coolbreeze inverter");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Coolbreeze Control", padding_width = PADDING_WIDTH);
@@ -418,16 +548,17 @@ pub fn set_coolbreeze_inverter(client: &Client, enable: bool) {
     let message = format!("Coolbreeze inverter {}.", status);
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_coolbreeze_resume_last(client: &Client, enable: bool) {
+pub fn set_coolbreeze_resume_last(client: &IzoneClient, enable: bool) -> Result<()> {
     const BOX_WIDTH: usize = 45;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let enable_val = if enable { 1 } else { 0 };
     let command_data = json!({"CoolbreezeResumeLast": enable_val});
-    make_command_request(client, command_data)
-        .expect("Failed to set Coolbreeze resume last state");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Coolbreeze Control", padding_width = PADDING_WIDTH);
@@ -436,20 +567,20 @@ pub fn set_coolbreeze_resume_last(client: &Client, enable: bool) {
     let message = format!("Resume last state {}.", status);
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_coolbreeze_fan_max_auto(client: &Client, speed: u8) {
-    if speed < 1 || speed > 100 {
-        eprintln!("{}", "Error: Fan max auto speed must be 1-100%".red());
-        exit(1);
+pub fn set_coolbreeze_fan_max_auto(client: &IzoneClient, speed: u8) -> Result<()> {
+    if !(1..=100).contains(&speed) {
+        return Err(IzoneError::validation("Fan max auto speed must be 1-100%"));
     }
 
     const BOX_WIDTH: usize = 50;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let command_data = json!({"CoolbreezeFanMaxAuto": speed});
-    make_command_request(client, command_data)
-        .expect("Failed to set Coolbreeze fan max auto");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Coolbreeze Control", padding_width = PADDING_WIDTH);
@@ -457,20 +588,20 @@ pub fn set_coolbreeze_fan_max_auto(client: &Client, speed: u8) {
     let message = format!("Fan max auto speed set to {}%.", speed.to_string().green());
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_coolbreeze_fan_max(client: &Client, speed: u8) {
-    if speed < 1 || speed > 100 {
-        eprintln!("{}", "Error: Fan max speed must be 1-100%".red());
-        exit(1);
+pub fn set_coolbreeze_fan_max(client: &IzoneClient, speed: u8) -> Result<()> {
+    if !(1..=100).contains(&speed) {
+        return Err(IzoneError::validation("Fan max speed must be 1-100%"));
     }
 
     const BOX_WIDTH: usize = 50;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let command_data = json!({"CoolbreezeFanMax": speed});
-    make_command_request(client, command_data)
-        .expect("Failed to set Coolbreeze fan max");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Coolbreeze Control", padding_width = PADDING_WIDTH);
@@ -478,20 +609,20 @@ pub fn set_coolbreeze_fan_max(client: &Client, speed: u8) {
     let message = format!("Fan max speed set to {}%.", speed.to_string().green());
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_coolbreeze_exhaust_max(client: &Client, speed: u8) {
-    if speed < 1 || speed > 100 {
-        eprintln!("{}", "Error: Exhaust max speed must be 1-100%".red());
-        exit(1);
+pub fn set_coolbreeze_exhaust_max(client: &IzoneClient, speed: u8) -> Result<()> {
+    if !(1..=100).contains(&speed) {
+        return Err(IzoneError::validation("Exhaust max speed must be 1-100%"));
     }
 
     const BOX_WIDTH: usize = 50;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let command_data = json!({"CoolbreezeExhMax": speed});
-    make_command_request(client, command_data)
-        .expect("Failed to set Coolbreeze exhaust max");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Coolbreeze Control", padding_width = PADDING_WIDTH);
@@ -499,16 +630,17 @@ pub fn set_coolbreeze_exhaust_max(client: &Client, speed: u8) {
     let message = format!("Exhaust max speed set to {}%.", speed.to_string().green());
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_coolbreeze_exhaust_enable(client: &Client, enable: bool) {
+pub fn set_coolbreeze_exhaust_enable(client: &IzoneClient, enable: bool) -> Result<()> {
     const BOX_WIDTH: usize = 45;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let enable_val = if enable { 1 } else { 0 };
     let command_data = json!({"CoolbreezeExhEn": enable_val});
-    make_command_request(client, command_data)
-        .expect("Failed to set Coolbreeze exhaust enable");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Coolbreeze Control", padding_width = PADDING_WIDTH);
@@ -517,9 +649,11 @@ pub fn set_coolbreeze_exhaust_enable(client: &Client, enable: bool) {
     let message = format!("Exhaust mode {}.", status);
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_coolbreeze_control_sensor(client: &Client, sensor_type: &str) {
+pub fn set_coolbreeze_control_sensor(client: &IzoneClient, sensor_type: &str) -> Result<()> {
     const BOX_WIDTH: usize = 50;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
@@ -527,14 +661,12 @@ pub fn set_coolbreeze_control_sensor(client: &Client, sensor_type: &str) {
         "screen" => 0,
         "remote" => 1,
         _ => {
-            eprintln!("{}", "Error: Sensor type must be 'screen' or 'remote'".red());
-            exit(1);
+            return Err(IzoneError::validation("Sensor type must be 'screen' or 'remote'"));
         }
     };
 
     let command_data = json!({"CoolbreezeCtrlSens": sensor_val});
-    make_command_request(client, command_data)
-        .expect("Failed to set Coolbreeze control sensor");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Coolbreeze Control", padding_width = PADDING_WIDTH);
@@ -542,20 +674,20 @@ pub fn set_coolbreeze_control_sensor(client: &Client, sensor_type: &str) {
     let message = format!("Control sensor set to {}.", sensor_type.cyan());
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_coolbreeze_temp_calibration(client: &Client, calibration: i16) {
-    if calibration < -50 || calibration > 50 {
-        eprintln!("{}", "Error: Calibration must be -50 to 50 (-5.0°C to +5.0°C)".red());
-        exit(1);
+pub fn set_coolbreeze_temp_calibration(client: &IzoneClient, calibration: i16) -> Result<()> {
+    if !(-50..=50).contains(&calibration) {
+        return Err(IzoneError::validation("Calibration must be -50 to 50 (-5.0°C to +5.0°C)"));
     }
 
     const BOX_WIDTH: usize = 55;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let command_data = json!({"CoolbreezeCalibTemp": calibration});
-    make_command_request(client, command_data)
-        .expect("Failed to set Coolbreeze temperature calibration");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Coolbreeze Control", padding_width = PADDING_WIDTH);
@@ -564,20 +696,20 @@ pub fn set_coolbreeze_temp_calibration(client: &Client, calibration: i16) {
     let message = format!("Temperature calibration set to {}°C.", format!("{:+.1}", calib_val).green());
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_coolbreeze_temp_deadband(client: &Client, deadband: u16) {
-    if deadband < 100 || deadband > 500 {
-        eprintln!("{}", "Error: Deadband must be 100-500 (1.0-5.0°C)".red());
-        exit(1);
+pub fn set_coolbreeze_temp_deadband(client: &IzoneClient, deadband: u16) -> Result<()> {
+    if !(100..=500).contains(&deadband) {
+        return Err(IzoneError::validation("Deadband must be 100-500 (1.0-5.0°C)"));
     }
 
     const BOX_WIDTH: usize = 55;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let command_data = json!({"CoolbreezeDeadTemp": deadband});
-    make_command_request(client, command_data)
-        .expect("Failed to set Coolbreeze temperature deadband");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Coolbreeze Control", padding_width = PADDING_WIDTH);
@@ -586,20 +718,20 @@ pub fn set_coolbreeze_temp_deadband(client: &Client, deadband: u16) {
     let message = format!("Temperature deadband set to {}°C.", format!("{:.1}", deadband_val).green());
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_coolbreeze_auto_fan_max_time(client: &Client, time_minutes: u8) {
+pub fn set_coolbreeze_auto_fan_max_time(client: &IzoneClient, time_minutes: u8) -> Result<()> {
     if time_minutes > 60 {
-        eprintln!("{}", "Error: Auto fan max time must be 0-60 minutes".red());
-        exit(1);
+        return Err(IzoneError::validation("Auto fan max time must be 0-60 minutes"));
     }
 
     const BOX_WIDTH: usize = 55;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let command_data = json!({"CoolbreezeAutoFanMaxTime": time_minutes});
-    make_command_request(client, command_data)
-        .expect("Failed to set Coolbreeze auto fan max time");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Coolbreeze Control", padding_width = PADDING_WIDTH);
@@ -607,22 +739,22 @@ pub fn set_coolbreeze_auto_fan_max_time(client: &Client, time_minutes: u8) {
     let message = format!("Auto fan max time set to {} minutes.", time_minutes.to_string().green());
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
 // ==================== VENTILATION COMMANDS ====================
 
-pub fn set_ventilation_rh_setpoint(client: &Client, rh: u8) {
-    if rh < 5 || rh > 95 {
-        eprintln!("{}", "Error: RH setpoint must be 5-95%".red());
-        exit(1);
+pub fn set_ventilation_rh_setpoint(client: &IzoneClient, rh: u8) -> Result<()> {
+    if !(5..=95).contains(&rh) {
+        return Err(IzoneError::validation("RH setpoint must be 5-95%"));
     }
 
     const BOX_WIDTH: usize = 50;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let command_data = json!({"VentilationRfSetpoint": rh});
-    make_command_request(client, command_data)
-        .expect("Failed to set ventilation RH setpoint");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Ventilation Control", padding_width = PADDING_WIDTH);
@@ -630,20 +762,20 @@ pub fn set_ventilation_rh_setpoint(client: &Client, rh: u8) {
     let message = format!("RH setpoint set to {}%.", rh.to_string().green());
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_ventilation_vocs_setpoint(client: &Client, vocs: u16) {
-    if vocs < 50 || vocs > 2500 {
-        eprintln!("{}", "Error: VOCs setpoint must be 50-2500 ppb".red());
-        exit(1);
+pub fn set_ventilation_vocs_setpoint(client: &IzoneClient, vocs: u16) -> Result<()> {
+    if !(50..=2500).contains(&vocs) {
+        return Err(IzoneError::validation("VOCs setpoint must be 50-2500 ppb"));
     }
 
     const BOX_WIDTH: usize = 50;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let command_data = json!({"VentilationVocsSetpoint": vocs});
-    make_command_request(client, command_data)
-        .expect("Failed to set ventilation VOCs setpoint");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Ventilation Control", padding_width = PADDING_WIDTH);
@@ -651,20 +783,20 @@ pub fn set_ventilation_vocs_setpoint(client: &Client, vocs: u16) {
     let message = format!("VOCs setpoint set to {} ppb.", vocs.to_string().green());
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_ventilation_eco2_setpoint(client: &Client, eco2: u16) {
-    if eco2 < 500 || eco2 > 1500 {
-        eprintln!("{}", "Error: eCO2 setpoint must be 500-1500 ppm".red());
-        exit(1);
+pub fn set_ventilation_eco2_setpoint(client: &IzoneClient, eco2: u16) -> Result<()> {
+    if !(500..=1500).contains(&eco2) {
+        return Err(IzoneError::validation("eCO2 setpoint must be 500-1500 ppm"));
     }
 
     const BOX_WIDTH: usize = 50;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let command_data = json!({"VentilationEco2Setpoint": eco2});
-    make_command_request(client, command_data)
-        .expect("Failed to set ventilation eCO2 setpoint");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Ventilation Control", padding_width = PADDING_WIDTH);
@@ -672,20 +804,20 @@ pub fn set_ventilation_eco2_setpoint(client: &Client, eco2: u16) {
     let message = format!("eCO2 setpoint set to {} ppm.", eco2.to_string().green());
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_ventilation_fan_stage_delay(client: &Client, delay_minutes: u8) {
-    if delay_minutes < 3 || delay_minutes > 240 {
-        eprintln!("{}", "Error: Fan stage delay must be 3-240 minutes".red());
-        exit(1);
+pub fn set_ventilation_fan_stage_delay(client: &IzoneClient, delay_minutes: u8) -> Result<()> {
+    if !(3..=240).contains(&delay_minutes) {
+        return Err(IzoneError::validation("Fan stage delay must be 3-240 minutes"));
     }
 
     const BOX_WIDTH: usize = 50;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let command_data = json!({"VentilationFanStageDelay": delay_minutes});
-    make_command_request(client, command_data)
-        .expect("Failed to set ventilation fan stage delay");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Ventilation Control", padding_width = PADDING_WIDTH);
@@ -693,16 +825,17 @@ pub fn set_ventilation_fan_stage_delay(client: &Client, delay_minutes: u8) {
     let message = format!("Fan stage delay set to {} min.", delay_minutes.to_string().green());
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_ventilation_cycle_fan_off(client: &Client, enable: bool) {
+pub fn set_ventilation_cycle_fan_off(client: &IzoneClient, enable: bool) -> Result<()> {
     const BOX_WIDTH: usize = 50;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let enable_val = if enable { 1 } else { 0 };
     let command_data = json!({"VentilationCycleFanOff": enable_val});
-    make_command_request(client, command_data)
-        .expect("Failed to set ventilation cycle fan off");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Ventilation Control", padding_width = PADDING_WIDTH);
@@ -711,16 +844,17 @@ pub fn set_ventilation_cycle_fan_off(client: &Client, enable: bool) {
     let message = format!("Cycle fan off {}.", status);
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_ventilation_use_rh_control(client: &Client, enable: bool) {
+pub fn set_ventilation_use_rh_control(client: &IzoneClient, enable: bool) -> Result<()> {
     const BOX_WIDTH: usize = 50;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let enable_val = if enable { 1 } else { 0 };
     let command_data = json!({"VentilationUseRhControl": enable_val});
-    make_command_request(client, command_data)
-        .expect("Failed to set ventilation use RH control");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Ventilation Control", padding_width = PADDING_WIDTH);
@@ -729,16 +863,17 @@ pub fn set_ventilation_use_rh_control(client: &Client, enable: bool) {
     let message = format!("Use RH control {}.", status);
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_ventilation_use_vocs_control(client: &Client, enable: bool) {
+pub fn set_ventilation_use_vocs_control(client: &IzoneClient, enable: bool) -> Result<()> {
     const BOX_WIDTH: usize = 50;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let enable_val = if enable { 1 } else { 0 };
     let command_data = json!({"VentilationUseVcosControl": enable_val});
-    make_command_request(client, command_data)
-        .expect("Failed to set ventilation use VOCs control");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Ventilation Control", padding_width = PADDING_WIDTH);
@@ -747,16 +882,17 @@ pub fn set_ventilation_use_vocs_control(client: &Client, enable: bool) {
     let message = format!("Use VOCs control {}.", status);
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_ventilation_use_eco2_control(client: &Client, enable: bool) {
+pub fn set_ventilation_use_eco2_control(client: &IzoneClient, enable: bool) -> Result<()> {
     const BOX_WIDTH: usize = 50;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let enable_val = if enable { 1 } else { 0 };
     let command_data = json!({"VentilationUseEco2Control": enable_val});
-    make_command_request(client, command_data)
-        .expect("Failed to set ventilation use eCO2 control");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Ventilation Control", padding_width = PADDING_WIDTH);
@@ -765,23 +901,22 @@ pub fn set_ventilation_use_eco2_control(client: &Client, enable: bool) {
     let message = format!("Use eCO2 control {}.", status);
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
 // ==================== SYSTEM CONFIGURATION COMMANDS ====================
 
-pub fn set_system_setpoint(client: &Client, setpoint: f32) {
-    if setpoint < 15.0 || setpoint > 30.0 {
-        eprintln!("{}", "Error: Setpoint must be 15.0-30.0°C".red());
-        exit(1);
+pub fn set_system_setpoint(client: &IzoneClient, setpoint: f32) -> Result<()> {
+    if !(15.0..=30.0).contains(&setpoint) {
+        return Err(IzoneError::validation("Setpoint must be 15.0-30.0°C"));
     }
 
     const BOX_WIDTH: usize = 45;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let setpoint_int = (setpoint * 100.0).round() as u32;
-    let command_data = json!({"SysSetpoint": setpoint_int});
-    make_command_request(client, command_data)
-        .expect("Failed to set system setpoint");
+    client.set_system_setpoint(setpoint_int)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "System Control", padding_width = PADDING_WIDTH);
@@ -789,15 +924,16 @@ pub fn set_system_setpoint(client: &Client, setpoint: f32) {
     let message = format!("System setpoint set to {:.1}°C.", setpoint);
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_sleep_timer(client: &Client, minutes: u32) {
+pub fn set_sleep_timer(client: &IzoneClient, minutes: u32) -> Result<()> {
     const BOX_WIDTH: usize = 45;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let command_data = json!({"SysSleepTimer": minutes});
-    make_command_request(client, command_data)
-        .expect("Failed to set sleep timer");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "System Control", padding_width = PADDING_WIDTH);
@@ -809,37 +945,34 @@ pub fn set_sleep_timer(client: &Client, minutes: u32) {
     };
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_economy_lock(client: &Client, enable: bool, min: Option<f32>, max: Option<f32>) {
+pub fn set_economy_lock(client: &IzoneClient, enable: bool, min: Option<f32>, max: Option<f32>) -> Result<()> {
     const BOX_WIDTH: usize = 55;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let enable_val = if enable { 1 } else { 0 };
     let command_data = json!({"EconomyLock": enable_val});
-    make_command_request(client, command_data.clone())
-        .expect("Failed to set economy lock");
+    client.command(command_data.clone())?;
 
     if let Some(min_temp) = min {
-        if min_temp < 15.0 || min_temp > 30.0 {
-            eprintln!("{}", "Error: Min temperature must be 15.0-30.0°C".red());
-            exit(1);
+        if !(15.0..=30.0).contains(&min_temp) {
+            return Err(IzoneError::validation("Min temperature must be 15.0-30.0°C"));
         }
         let min_int = (min_temp * 100.0).round() as u32;
         let min_cmd = json!({"EconomyMin": min_int});
-        make_command_request(client, min_cmd)
-            .expect("Failed to set economy min");
+        client.command(min_cmd)?;
     }
 
     if let Some(max_temp) = max {
-        if max_temp < 15.0 || max_temp > 30.0 {
-            eprintln!("{}", "Error: Max temperature must be 15.0-30.0°C".red());
-            exit(1);
+        if !(15.0..=30.0).contains(&max_temp) {
+            return Err(IzoneError::validation("Max temperature must be 15.0-30.0°C"));
         }
         let max_int = (max_temp * 100.0).round() as u32;
         let max_cmd = json!({"EconomyMax": max_int});
-        make_command_request(client, max_cmd)
-            .expect("Failed to set economy max");
+        client.command(max_cmd)?;
     }
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
@@ -858,20 +991,20 @@ pub fn set_economy_lock(client: &Client, enable: bool, min: Option<f32>, max: Op
     };
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_filter_warning(client: &Client, months: u8) {
+pub fn set_filter_warning(client: &IzoneClient, months: u8) -> Result<()> {
     if months != 0 && months != 3 && months != 6 && months != 12 {
-        eprintln!("{}", "Error: Filter warning must be 0 (disabled), 3, 6, or 12 months".red());
-        exit(1);
+        return Err(IzoneError::validation("Filter warning must be 0 (disabled), 3, 6, or 12 months"));
     }
 
     const BOX_WIDTH: usize = 50;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let command_data = json!({"FilterWarn": months});
-    make_command_request(client, command_data)
-        .expect("Failed to set filter warning");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "System Configuration", padding_width = PADDING_WIDTH);
@@ -883,15 +1016,593 @@ pub fn set_filter_warning(client: &Client, months: u8) {
     };
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn reset_warning(client: &Client, warning_type: &str) {
+pub fn set_rf_channel(client: &IzoneClient, channel: u8) -> Result<()> {
+    if !(1..=8).contains(&channel) {
+        return Err(IzoneError::validation("RF channel must be 1-8"));
+    }
+
+    const BOX_WIDTH: usize = 50;
+    const PADDING_WIDTH: usize = BOX_WIDTH - 2;
+
+    let before = client.system()?.rf_ch;
+    client.command(api::change_rf_channel_command(channel))?;
+    let after = client.system()?.rf_ch;
+    if after != channel {
+        return Err(IzoneError::Controller {
+            url: client.base_url().to_string(),
+            message: format!("RF channel still reports {} after ChangeRfCh {}.", after, channel),
+        });
+    }
+
+    println!("╔{}╗", "═".repeat(BOX_WIDTH));
+    println!("║ {:^padding_width$} ║", "System Configuration", padding_width = PADDING_WIDTH);
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+    let message = format!("RF channel changed from {} to {}.", before, channel.to_string().green());
+    println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
+    println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
+}
+
+pub fn set_return_air_sensor(client: &IzoneClient, source: &str) -> Result<()> {
+    let ras = get_ras_value(source).ok_or_else(|| {
+        IzoneError::validation(format!("Unknown control source '{}'.\nAvailable sources: ras, master, zones.", source))
+    })?;
+
+    const BOX_WIDTH: usize = 50;
+    const PADDING_WIDTH: usize = BOX_WIDTH - 2;
+
+    client.command(api::ras_set_command(ras))?;
+    let after = client.system()?;
+    if after.ras != ras {
+        return Err(IzoneError::Controller {
+            url: client.base_url().to_string(),
+            message: format!("Control source still reports {} after RASSet {}.", get_control_source_text(after.ras), ras),
+        });
+    }
+
+    println!("╔{}╗", "═".repeat(BOX_WIDTH));
+    println!("║ {:^padding_width$} ║", "System Configuration", padding_width = PADDING_WIDTH);
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+    let message = format!("Control source set to {}.", get_control_source_text(ras).green());
+    println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
+    println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
+}
+
+pub fn set_master_zone(client: &IzoneClient, zones: &ZoneMap, zone_name: &str) -> Result<()> {
+    let zone_index = zones.resolve_strict(zone_name)?;
+    let zone_name = zones.name_of(zone_index).unwrap_or(zone_name);
+
+    const BOX_WIDTH: usize = 50;
+    const PADDING_WIDTH: usize = BOX_WIDTH - 2;
+
+    client.command(api::master_zone_command(zone_index))?;
+    let after = client.system()?;
+    // CtrlZone only has to follow the master zone while the master zone is the control source
+    if after.ras != 2 {
+        log::warn!(
+            "The control source is {}; the master zone is only used with 'izone config return-air-sensor master'.",
+            get_control_source_text(after.ras)
+        );
+        if after.ctrl_zone != zone_index {
+            log::warn!("Control zone reports {} after MasterZone {}.", after.ctrl_zone, zone_index);
+        }
+    } else if after.ctrl_zone != zone_index {
+        return Err(IzoneError::Controller {
+            url: client.base_url().to_string(),
+            message: format!("Control zone still reports {} after MasterZone {}.", after.ctrl_zone, zone_index),
+        });
+    }
+
+    println!("╔{}╗", "═".repeat(BOX_WIDTH));
+    println!("║ {:^padding_width$} ║", "System Configuration", padding_width = PADDING_WIDTH);
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+    let message = format!("Master zone set to '{}'.", zone_name.green());
+    println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
+    println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
+}
+
+/// Shows the tag lines, or sets the ones given and reads them back.
+pub fn set_system_tags(client: &IzoneClient, line1: Option<&str>, line2: Option<&str>) -> Result<()> {
+    for text in [line1, line2].into_iter().flatten() {
+        if !text.is_ascii() || text.chars().any(|c| c.is_ascii_control()) {
+            return Err(IzoneError::validation(format!("Tag line '{}' must be printable ASCII.", text)));
+        }
+        if text.len() > api::SYS_TAG_MAX_LEN {
+            return Err(IzoneError::validation(format!(
+                "Tag line '{}' is {} characters; the controller stores at most {}.",
+                text,
+                text.len(),
+                api::SYS_TAG_MAX_LEN
+            )));
+        }
+    }
+
+    if let Some(text) = line1 {
+        client.command(api::sys_tag_command(1, text))?;
+    }
+    if let Some(text) = line2 {
+        client.command(api::sys_tag_command(2, text))?;
+    }
+
+    let after = client.system()?;
+    for (line, wanted, actual) in [(1, line1, &after.tag1), (2, line2, &after.tag2)] {
+        if wanted.is_some_and(|wanted| actual != wanted) {
+            return Err(IzoneError::Controller {
+                url: client.base_url().to_string(),
+                message: format!("Tag line {} still reads '{}' after SysTag{}.", line, actual, line),
+            });
+        }
+    }
+
+    const BOX_WIDTH: usize = 50;
+    const PADDING_WIDTH: usize = BOX_WIDTH - 2;
+
+    let changed = line1.is_some() || line2.is_some();
+    println!("╔{}╗", "═".repeat(BOX_WIDTH));
+    println!("║ {:^padding_width$} ║", if changed { "System Configuration" } else { "System Tags" }, padding_width = PADDING_WIDTH);
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+    for (line, text) in [(1, &after.tag1), (2, &after.tag2)] {
+        let message = format!("Line {}: {}", line, text.green());
+        println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
+    }
+    println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
+}
+
+/// Reads a new configuration password (no-echo prompt with confirmation, or a
+/// line from stdin), sets it with `ChangePass` and reads it back.
+pub fn set_config_password(client: &IzoneClient, from_stdin: bool) -> Result<()> {
+    let prompt_error = |e: std::io::Error| IzoneError::validation(format!("Could not read the password: {}\nUse --stdin when not running in a terminal.", e));
+
+    let password = if from_stdin {
+        let mut line = String::new();
+        std::io::stdin().read_line(&mut line).map_err(prompt_error)?;
+        line.trim_end_matches(['\r', '\n']).to_string()
+    } else {
+        let password = rpassword::prompt_password("New configuration password: ").map_err(prompt_error)?;
+        let confirm = rpassword::prompt_password("Confirm password: ").map_err(prompt_error)?;
+        if password != confirm {
+            return Err(IzoneError::validation("Passwords do not match."));
+        }
+        password
+    };
+
+    if password.is_empty() {
+        return Err(IzoneError::validation("The password cannot be empty."));
+    }
+    if !password.is_ascii() || password.chars().any(|c| c.is_ascii_control()) {
+        return Err(IzoneError::validation("The password must be printable ASCII."));
+    }
+    if password.len() > api::PASSWORD_MAX_LEN {
+        return Err(IzoneError::validation(format!(
+            "The password is {} characters; the controller stores at most {}.",
+            password.len(),
+            api::PASSWORD_MAX_LEN
+        )));
+    }
+
+    client.command(api::change_pass_command(&password))?;
+    if client.system()?.pass.expose() != password {
+        return Err(IzoneError::Controller {
+            url: client.base_url().to_string(),
+            message: "The configuration password did not change after ChangePass.".to_string(),
+        });
+    }
+
+    const BOX_WIDTH: usize = 50;
+    const PADDING_WIDTH: usize = BOX_WIDTH - 2;
+
+    println!("╔{}╗", "═".repeat(BOX_WIDTH));
+    println!("║ {:^padding_width$} ║", "System Configuration", padding_width = PADDING_WIDTH);
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+    let message = format!("Configuration password {}.", "changed".green());
+    println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
+    println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
+}
+
+/// Zones 0..count as the controller reports them.
+fn read_zone_list(client: &IzoneClient, count: u8) -> Result<Vec<ZonesV2>> {
+    (0..count).map(|index| client.zone(index)).collect()
+}
+
+/// "Kitchen (Auto)" or "Work (Constant 1)".
+fn describe_zone(zone: &ZonesV2) -> String {
+    if zone.zone_type == 2 {
+        format!("{} ({} {})", zone.name, get_zone_type_text(zone.zone_type), zone.constant)
+    } else {
+        format!("{} ({})", zone.name, get_zone_type_text(zone.zone_type))
+    }
+}
+
+/// Warns about every zone already configured as Constant; `affected` says
+/// why a zone matters for this change, if it does.
+fn warn_constant_zones(zones: &[ZonesV2], affected: impl Fn(u8, &ZonesV2) -> Option<&'static str>) {
+    for (index, zone) in zones.iter().enumerate().filter(|(_, zone)| zone.zone_type == 2) {
+        let reason = affected(index as u8, zone).map(|r| format!("; {}", r)).unwrap_or_default();
+        log::warn!("Zone '{}' is already configured as Constant {}{}.", zone.name, zone.constant, reason);
+    }
+}
+
+/// Prints the zone list before and after an installer change, marking removed
+/// (-), added (+) and changed (~) zones.
+fn print_zone_list_diff(summary: &str, before: &[ZonesV2], after: &[ZonesV2]) {
+    const BOX_WIDTH: usize = 60;
+    const PADDING_WIDTH: usize = BOX_WIDTH - 2;
+
+    let print_line = |message: String| {
+        let spaces_needed = PADDING_WIDTH.saturating_sub(get_visible_length(&message));
+        println!("║ {}{} ║", message, " ".repeat(spaces_needed));
+    };
+
+    println!("╔{}╗", "═".repeat(BOX_WIDTH));
+    println!("║ {:^padding_width$} ║", "System Configuration", padding_width = PADDING_WIDTH);
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+    print_line(summary.to_string());
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+    for index in 0..before.len().max(after.len()) {
+        let line = match (before.get(index), after.get(index)) {
+            (Some(old), None) => format!("- {:>2}  {}", index, describe_zone(old)).red().to_string(),
+            (None, Some(new)) => format!("+ {:>2}  {}", index, describe_zone(new)).green().to_string(),
+            (Some(old), Some(new)) if describe_zone(old) != describe_zone(new) => {
+                format!("~ {:>2}  {} -> {}", index, describe_zone(old), describe_zone(new)).yellow().to_string()
+            }
+            (Some(old), Some(_)) => format!("  {:>2}  {}", index, describe_zone(old)),
+            (None, None) => continue,
+        };
+        print_line(line);
+    }
+    println!("╚{}╝", "═".repeat(BOX_WIDTH));
+}
+
+/// Sets the number of installed zones (`NoOfZones`). Needs `force`, since
+/// zones beyond the new count disappear from the controller.
+pub fn set_zone_count(client: &IzoneClient, zones: u8, force: bool) -> Result<()> {
+    if !(1..=MAX_ZONES).contains(&zones) {
+        return Err(IzoneError::validation(format!("Number of zones must be 1-{}", MAX_ZONES)));
+    }
+
+    let sys = client.system()?;
+    if sys.no_of_zones == zones {
+        println!("The system already has {} zones; nothing to change.", zones);
+        return Ok(());
+    }
+
+    // NoOfConstants may not exceed NoOfZones, and --force does not override that
+    if sys.no_of_const > zones {
+        return Err(IzoneError::validation(format!(
+            "The system has {} constants, more than the new {} zones (NoOfConstants must not exceed NoOfZones).\nLower them first with 'izone config constant-count {} --force'.",
+            sys.no_of_const, zones, zones
+        )));
+    }
+
+    let before = read_zone_list(client, sys.no_of_zones)?;
+    warn_constant_zones(&before, |index, _| (index >= zones).then_some("it will be removed"));
+
+    if !force {
+        return Err(IzoneError::validation(format!(
+            "Changing the number of zones from {} to {} is an installer setting.\nRe-run with --force to apply it.",
+            sys.no_of_zones, zones
+        )));
+    }
+
+    client.command(api::no_of_zones_command(zones))?;
+    let after_sys = client.system()?;
+    if after_sys.no_of_zones != zones {
+        return Err(IzoneError::Controller {
+            url: client.base_url().to_string(),
+            message: format!("NoOfZones still reports {} after setting it to {}.", after_sys.no_of_zones, zones),
+        });
+    }
+    let after = read_zone_list(client, after_sys.no_of_zones)?;
+
+    print_zone_list_diff(
+        &format!("Number of zones changed from {} to {}.", sys.no_of_zones, zones.to_string().green()),
+        &before,
+        &after,
+    );
+
+    Ok(())
+}
+
+/// Sets the number of constants (`NoOfConstants`, at most `NoOfZones`).
+/// Needs `force`, since zones using a removed constant stop being constant.
+pub fn set_constant_count(client: &IzoneClient, constants: u8, force: bool) -> Result<()> {
+    let sys = client.system()?;
+    if constants > sys.no_of_zones {
+        return Err(IzoneError::validation(format!(
+            "Number of constants must be 0-{} (NoOfZones = {}).",
+            sys.no_of_zones, sys.no_of_zones
+        )));
+    }
+    if sys.no_of_const == constants {
+        println!("The system already has {} constants; nothing to change.", constants);
+        return Ok(());
+    }
+
+    let before = read_zone_list(client, sys.no_of_zones)?;
+    warn_constant_zones(&before, |_, zone| (zone.constant > constants).then_some("that constant will no longer exist"));
+
+    if !force {
+        return Err(IzoneError::validation(format!(
+            "Changing the number of constants from {} to {} is an installer setting.\nRe-run with --force to apply it.",
+            sys.no_of_const, constants
+        )));
+    }
+
+    client.command(api::no_of_constants_command(constants))?;
+    let after_sys = client.system()?;
+    if after_sys.no_of_const != constants {
+        return Err(IzoneError::Controller {
+            url: client.base_url().to_string(),
+            message: format!("NoOfConst still reports {} after setting it to {}.", after_sys.no_of_const, constants),
+        });
+    }
+    let after = read_zone_list(client, after_sys.no_of_zones)?;
+
+    print_zone_list_diff(
+        &format!("Number of constants changed from {} to {}.", sys.no_of_const, constants.to_string().green()),
+        &before,
+        &after,
+    );
+
+    Ok(())
+}
+
+fn print_linkage_matrix(title: &str, linkage: &LinkageReport) {
+    const FUNCTION_COL_WIDTH: usize = 12;
+    const FLAG_COL_WIDTH: usize = 16;
+    const BOX_WIDTH: usize = FUNCTION_COL_WIDTH + 2 * FLAG_COL_WIDTH + 4;
+    const PADDING_WIDTH: usize = BOX_WIDTH - 2;
+
+    let flag = |enabled: bool| if enabled { "Enabled".green().to_string() } else { "Disabled".red().to_string() };
+
+    println!("╔{}╗", "═".repeat(BOX_WIDTH));
+    println!("║ {:^padding_width$} ║", title, padding_width = PADDING_WIDTH);
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+    println!(
+        "║ {:<FUNCTION_COL_WIDTH$} {:<FLAG_COL_WIDTH$} {:<FLAG_COL_WIDTH$} ║",
+        "FUNCTION", "IZONE -> UNIT", "UNIT -> IZONE"
+    );
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+    for (function, izone, unit) in [
+        ("On/Off", linkage.izone_on_off, linkage.unit_on_off),
+        ("Mode", linkage.izone_mode, linkage.unit_mode),
+        ("Fan", linkage.izone_fan, linkage.unit_fan),
+        ("Setpoint", linkage.izone_setpoint, linkage.unit_setpoint),
+    ] {
+        let (izone, unit) = (flag(izone), flag(unit));
+        println!(
+            "║ {:<FUNCTION_COL_WIDTH$} {:<izone_width$} {:<unit_width$} ║",
+            function,
+            izone,
+            unit,
+            izone_width = FLAG_COL_WIDTH + izone.len() - get_visible_length(&izone),
+            unit_width = FLAG_COL_WIDTH + unit.len() - get_visible_length(&unit),
+        );
+    }
+    println!("╚{}╝", "═".repeat(BOX_WIDTH));
+}
+
+/// Shows which AC unit functions iZone controls and which iZone functions the
+/// unit's own controller controls.
+pub fn get_linkage(client: &IzoneClient, output: OutputFormat) -> Result<()> {
+    let linkage = LinkageReport::from(&client.system()?);
+
+    if output != OutputFormat::Table {
+        return print_record(output, &linkage);
+    }
+
+    print_linkage_matrix("UNIT LINKAGE", &linkage);
+    Ok(())
+}
+
+/// Enables or disables one linkage flag and reads it back.
+pub fn set_linkage(client: &IzoneClient, direction: &str, function: &str, enable: bool) -> Result<()> {
+    let key = get_linkage_key(direction, function).ok_or_else(|| {
+        IzoneError::validation(format!(
+            "Unknown linkage '{} {}'.\nDirections: izone (iZone controls the unit), unit (the unit controls iZone).\nFunctions: on-off, mode, fan, setpoint.",
+            direction, function
+        ))
+    })?;
+
+    client.command(api::linkage_command(key, enable))?;
+    let linkage = LinkageReport::from(&client.system()?);
+    if linkage.get(key) != Some(enable) {
+        return Err(IzoneError::Controller {
+            url: client.base_url().to_string(),
+            message: format!("{} still reports {} after setting it to {}.", key, !enable as u8, enable as u8),
+        });
+    }
+
+    print_linkage_matrix(&format!("{} {}", key, if enable { "Enabled" } else { "Disabled" }), &linkage);
+    Ok(())
+}
+
+fn print_panel(title: &str, panel: &PanelReport) {
+    const BOX_WIDTH: usize = 50;
+    const PADDING_WIDTH: usize = BOX_WIDTH - 2;
+    const LABEL_WIDTH: usize = 36;
+
+    let flag = |enabled: bool| if enabled { "Enabled".green().to_string() } else { "Disabled".red().to_string() };
+
+    println!("╔{}╗", "═".repeat(BOX_WIDTH));
+    println!("║ {:^padding_width$} ║", title, padding_width = PADDING_WIDTH);
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+    for (label, enabled) in [
+        ("Auto off (auto-off):", panel.auto_off),
+        ("Room temp display (room-temp):", panel.room_temp_display),
+        ("Wired sensor LEDs (wired-leds):", panel.wired_leds),
+        ("Hide induct temp (hide-induct):", panel.hide_induct),
+        ("Actual temps (show-actual-temps):", panel.show_actual_temps),
+        ("Out-of-view RAS (read-only):", panel.out_of_view_ras),
+    ] {
+        let value = flag(enabled);
+        let padding = PADDING_WIDTH.saturating_sub(LABEL_WIDTH + get_visible_length(&value));
+        println!("║ {:<LABEL_WIDTH$}{}{} ║", label, value, " ".repeat(padding));
+    }
+    println!("╚{}╝", "═".repeat(BOX_WIDTH));
+}
+
+/// Shows the installer and display flags of the controller panel.
+pub fn get_panel(client: &IzoneClient, output: OutputFormat) -> Result<()> {
+    let panel = PanelReport::from(&client.system()?);
+
+    if output != OutputFormat::Table {
+        return print_record(output, &panel);
+    }
+
+    print_panel("PANEL SETTINGS", &panel);
+    Ok(())
+}
+
+/// Enables or disables one panel flag and reads it back.
+pub fn set_panel(client: &IzoneClient, setting: &str, enable: bool) -> Result<()> {
+    if setting.eq_ignore_ascii_case("out-of-view-ras") {
+        return Err(IzoneError::validation(
+            "OutOfViewRAS has no documented command, so it can only be shown ('izone config panel').",
+        ));
+    }
+    let key = get_panel_key(setting).ok_or_else(|| {
+        IzoneError::validation(format!(
+            "Unknown panel setting '{}'.\nAvailable settings: auto-off, room-temp, wired-leds, hide-induct, show-actual-temps.",
+            setting
+        ))
+    })?;
+
+    client.command(api::panel_command(key, enable))?;
+    let panel = PanelReport::from(&client.system()?);
+    if panel.get(key) != Some(enable) {
+        return Err(IzoneError::Controller {
+            url: client.base_url().to_string(),
+            message: format!("{} still reports {} after setting it to {}.", key, !enable as u8, enable as u8),
+        });
+    }
+
+    print_panel(&format!("{} {}", key, if enable { "Enabled" } else { "Disabled" }), &panel);
+    Ok(())
+}
+
+/// Lowercase, comma-separated SysFan_e names ("low, high, auto").
+fn fan_speed_list(speeds: &[u8]) -> String {
+    speeds.iter().map(|&speed| get_fan_speed_text(speed).to_lowercase()).collect::<Vec<_>>().join(", ")
+}
+
+fn print_fan_auto(title: &str, fan_auto: &FanAutoReport, sys_v2: &SystemV2) {
+    const BOX_WIDTH: usize = 50;
+    const PADDING_WIDTH: usize = BOX_WIDTH - 2;
+    const LABEL_WIDTH: usize = 16;
+
+    let line = |label: &str, value: String| {
+        let padding = PADDING_WIDTH.saturating_sub(LABEL_WIDTH + get_visible_length(&value));
+        println!("║ {:<LABEL_WIDTH$}{}{} ║", label, value, " ".repeat(padding));
+    };
+
+    println!("╔{}╗", "═".repeat(BOX_WIDTH));
+    println!("║ {:^padding_width$} ║", title, padding_width = PADDING_WIDTH);
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+    line("Fan auto:", match fan_auto.enabled {
+        Some(true) => "Enabled".green().to_string(),
+        Some(false) => "Disabled".red().to_string(),
+        None => "Not reported".yellow().to_string(),
+    });
+    line("Fan type:", get_fan_auto_type_text(sys_v2.fan_auto_type));
+    line("Fan airflow:", fan_auto.fan_capacity.to_string().cyan().to_string());
+    line("Unit capacity:", format!("{} kW", fan_auto.unit_capacity_kw).cyan().to_string());
+    line("Fan speeds:", fan_speed_list(get_allowed_fan_speeds(sys_v2.fan_auto_en, sys_v2.fan_auto_type)));
+    println!("╚{}╝", "═".repeat(BOX_WIDTH));
+}
+
+/// Shows the fan auto settings and the fan speeds they allow.
+pub fn get_fan_auto(client: &IzoneClient, output: OutputFormat) -> Result<()> {
+    let sys_v2 = client.system()?;
+    let fan_auto = FanAutoReport::from(&sys_v2);
+
+    if output != OutputFormat::Table {
+        return print_record(output, &fan_auto);
+    }
+
+    print_fan_auto("FAN AUTO", &fan_auto, &sys_v2);
+    Ok(())
+}
+
+/// Sends one fan auto setting, reads `field` back and shows the result.
+fn apply_fan_auto(
+    client: &IzoneClient,
+    field: &str,
+    command: serde_json::Value,
+    wanted: u16,
+    read: fn(&SystemV2) -> u16,
+) -> Result<()> {
+    client.command(command)?;
+    let sys_v2 = client.system()?;
+    let actual = read(&sys_v2);
+    if actual != wanted {
+        return Err(IzoneError::Controller {
+            url: client.base_url().to_string(),
+            message: format!("{} still reports {} after setting it to {}.", field, actual, wanted),
+        });
+    }
+
+    print_fan_auto(&format!("{} Updated", field), &FanAutoReport::from(&sys_v2), &sys_v2);
+    Ok(())
+}
+
+/// Enables or disables the fan auto function (`FanAutoEn`).
+pub fn set_fan_auto_enable(client: &IzoneClient, enable: bool) -> Result<()> {
+    apply_fan_auto(client, "FanAutoEn", api::fan_auto_enable_command(enable), enable as u16, |sys| {
+        sys.fan_auto_en.unwrap_or_default() as u16
+    })
+}
+
+/// Sets the fan type the fan auto function drives (`FanAutoType`).
+pub fn set_fan_auto_type(client: &IzoneClient, fan_type: &str) -> Result<()> {
+    let value = get_fan_auto_type_value(fan_type).ok_or_else(|| {
+        IzoneError::validation(format!(
+            "Unknown fan type '{}'.\nAvailable fan types: 2-speed, 3-speed, variable, 4-speed.",
+            fan_type
+        ))
+    })?;
+    apply_fan_auto(client, "FanAutoType", api::fan_auto_type_command(value), value as u16, |sys| {
+        sys.fan_auto_type as u16
+    })
+}
+
+/// Sets the fan airflow rating used by fan auto (`FanCapacity`).
+pub fn set_fan_capacity(client: &IzoneClient, airflow: u16) -> Result<()> {
+    if airflow == 0 {
+        return Err(IzoneError::validation("Fan airflow must be greater than 0"));
+    }
+    apply_fan_auto(client, "FanCapacity", api::fan_capacity_command(airflow), airflow, |sys| sys.fan_capacity)
+}
+
+/// Sets the AC unit capacity in kW used by fan auto (`FanUnitCapacity`).
+pub fn set_fan_unit_capacity(client: &IzoneClient, kw: u16) -> Result<()> {
+    if kw == 0 {
+        return Err(IzoneError::validation("Unit capacity must be greater than 0 kW"));
+    }
+    apply_fan_auto(client, "FanUnitCapacity", api::fan_unit_capacity_command(kw), kw, |sys| {
+        sys.fan_unit_capacity
+    })
+}
+
+pub fn reset_warning(client: &IzoneClient, warning_type: &str) -> Result<()> {
     const BOX_WIDTH: usize = 50;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let command_data = json!({"ResetWarning": warning_type});
-    make_command_request(client, command_data)
-        .expect("Failed to reset warning");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "System Configuration", padding_width = PADDING_WIDTH);
@@ -899,15 +1610,16 @@ pub fn reset_warning(client: &Client, warning_type: &str) {
     let message = format!("{} warning reset.", warning_type.to_pascal_case().green());
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_damper_time(client: &Client, seconds: u8) {
+pub fn set_damper_time(client: &IzoneClient, seconds: u8) -> Result<()> {
     const BOX_WIDTH: usize = 50;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let command_data = json!({"DamperTime": seconds});
-    make_command_request(client, command_data)
-        .expect("Failed to set damper time");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "System Configuration", padding_width = PADDING_WIDTH);
@@ -919,12 +1631,13 @@ pub fn set_damper_time(client: &Client, seconds: u8) {
     };
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_auto_mode_deadband(client: &Client, deadband: f32) {
-    if deadband < 0.75 || deadband > 5.0 {
-        eprintln!("{}", "Error: Auto mode deadband must be 0.75-5.0°C".red());
-        exit(1);
+pub fn set_auto_mode_deadband(client: &IzoneClient, deadband: f32) -> Result<()> {
+    if !(0.75..=5.0).contains(&deadband) {
+        return Err(IzoneError::validation("Auto mode deadband must be 0.75-5.0°C"));
     }
 
     const BOX_WIDTH: usize = 55;
@@ -932,8 +1645,7 @@ pub fn set_auto_mode_deadband(client: &Client, deadband: f32) {
 
     let deadband_int = (deadband * 100.0).round() as u16;
     let command_data = json!({"AutoModeDeadB": deadband_int});
-    make_command_request(client, command_data)
-        .expect("Failed to set auto mode deadband");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "System Configuration", padding_width = PADDING_WIDTH);
@@ -941,16 +1653,17 @@ pub fn set_auto_mode_deadband(client: &Client, deadband: f32) {
     let message = format!("Auto mode deadband set to {:.2}°C.", deadband);
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_airflow_lock(client: &Client, enable: bool) {
+pub fn set_airflow_lock(client: &IzoneClient, enable: bool) -> Result<()> {
     const BOX_WIDTH: usize = 50;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let enable_val = if enable { 1 } else { 0 };
     let command_data = json!({"AirflowLock": enable_val});
-    make_command_request(client, command_data)
-        .expect("Failed to set airflow lock");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "System Configuration", padding_width = PADDING_WIDTH);
@@ -959,16 +1672,17 @@ pub fn set_airflow_lock(client: &Client, enable: bool) {
     let message = format!("Airflow adjustment {}.", status);
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_airflow_min_lock(client: &Client, enable: bool) {
+pub fn set_airflow_min_lock(client: &IzoneClient, enable: bool) -> Result<()> {
     const BOX_WIDTH: usize = 50;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let enable_val = if enable { 1 } else { 0 };
     let command_data = json!({"AirflowMinLock": enable_val});
-    make_command_request(client, command_data)
-        .expect("Failed to set airflow min lock");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "System Configuration", padding_width = PADDING_WIDTH);
@@ -977,20 +1691,20 @@ pub fn set_airflow_min_lock(client: &Client, enable: bool) {
     let message = format!("Airflow min adjustment {}.", status);
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_static_pressure(client: &Client, level: u8) {
+pub fn set_static_pressure(client: &IzoneClient, level: u8) -> Result<()> {
     if level > 4 {
-        eprintln!("{}", "Error: Static pressure level must be 0-4".red());
-        exit(1);
+        return Err(IzoneError::validation("Static pressure level must be 0-4"));
     }
 
     const BOX_WIDTH: usize = 50;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let command_data = json!({"StaticP": level});
-    make_command_request(client, command_data)
-        .expect("Failed to set static pressure");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "System Configuration", padding_width = PADDING_WIDTH);
@@ -1003,16 +1717,17 @@ pub fn set_static_pressure(client: &Client, level: u8) {
     let message = format!("Static pressure set to level {} ({}).", level.to_string().green(), level_text.cyan());
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_open_dampers_when_off(client: &Client, enable: bool) {
+pub fn set_open_dampers_when_off(client: &IzoneClient, enable: bool) -> Result<()> {
     const BOX_WIDTH: usize = 55;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let enable_val = if enable { 1 } else { 0 };
     let command_data = json!({"OpenDampersWhenOff": enable_val});
-    make_command_request(client, command_data)
-        .expect("Failed to set open dampers when off");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "System Configuration", padding_width = PADDING_WIDTH);
@@ -1021,16 +1736,17 @@ pub fn set_open_dampers_when_off(client: &Client, enable: bool) {
     let message = format!("Open dampers when off {}.", status);
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_scrooge_mode(client: &Client, enable: bool) {
+pub fn set_scrooge_mode(client: &IzoneClient, enable: bool) -> Result<()> {
     const BOX_WIDTH: usize = 50;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let enable_val = if enable { 1 } else { 0 };
     let command_data = json!({"ScroogeMode": enable_val});
-    make_command_request(client, command_data)
-        .expect("Failed to set scrooge mode");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "System Configuration", padding_width = PADDING_WIDTH);
@@ -1039,16 +1755,17 @@ pub fn set_scrooge_mode(client: &Client, enable: bool) {
     let message = format!("Scrooge mode {}.", status);
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_reverse_dampers(client: &Client, enable: bool) {
+pub fn set_reverse_dampers(client: &IzoneClient, enable: bool) -> Result<()> {
     const BOX_WIDTH: usize = 50;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let enable_val = if enable { 1 } else { 0 };
     let command_data = json!({"ReverseDampers": enable_val});
-    make_command_request(client, command_data)
-        .expect("Failed to set reverse dampers");
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "System Configuration", padding_width = PADDING_WIDTH);
@@ -1057,21 +1774,21 @@ pub fn set_reverse_dampers(client: &Client, enable: bool) {
     let message = format!("Reverse dampers {}.", status);
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_constant_control_by_area(client: &Client, enable: bool, area: Option<u16>) {
+pub fn set_constant_control_by_area(client: &IzoneClient, enable: bool, area: Option<u16>) -> Result<()> {
     const BOX_WIDTH: usize = 60;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let enable_val = if enable { 1 } else { 0 };
     let command_data = json!({"CnstCtrlAreaEn": enable_val});
-    make_command_request(client, command_data)
-        .expect("Failed to set constant control by area enable");
+    client.command(command_data)?;
 
     if let Some(area_val) = area {
         let area_cmd = json!({"CnstCtrlArea": area_val});
-        make_command_request(client, area_cmd)
-            .expect("Failed to set constant control area");
+        client.command(area_cmd)?;
     }
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
@@ -1085,4 +1802,6 @@ pub fn set_constant_control_by_area(client: &Client, enable: bool, area: Option<
     };
     println!("║ {:<padding_width$} ║", message, padding_width = PADDING_WIDTH - get_visible_length(&message) + message.len());
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,277 @@
+// izone/src/commands/temperzone.rs
+
+use colored::Colorize;
+
+use izone::api;
+use izone::helpers::{
+    get_temperzone_fan_type_text, get_temperzone_fan_type_value, get_temperzone_mode_type_text,
+    get_temperzone_mode_type_value, get_visible_length,
+};
+use izone::report::{TemperzoneConfigReport, TemperzoneHistoryReport, TemperzoneStatusReport};
+use izone::{IzoneClient, IzoneError, Result};
+
+use crate::commands::output::{print_record, OutputFormat};
+
+const BOX_WIDTH: usize = 60;
+const PADDING_WIDTH: usize = BOX_WIDTH - 2;
+const LABEL_WIDTH: usize = 34;
+
+fn print_header(title: &str) {
+    println!("║ {:^PADDING_WIDTH$} ║", title);
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+}
+
+fn print_line(label: &str, value: String) {
+    let padding = PADDING_WIDTH.saturating_sub(LABEL_WIDTH + get_visible_length(&value));
+    println!("║ {:<LABEL_WIDTH$}{}{} ║", label, value, " ".repeat(padding));
+}
+
+fn temp(value: f64) -> String {
+    format!("{:.1}°C", value).cyan().to_string()
+}
+
+/// Active channels of a bitmask ("DI1, DI3"), or "none".
+fn channels(bits: &[String]) -> String {
+    if bits.is_empty() {
+        "none".normal().to_string()
+    } else {
+        bits.iter().map(|b| b.to_uppercase()).collect::<Vec<_>>().join(", ").green().to_string()
+    }
+}
+
+/// Prints the Temperzone outdoor unit readings (request type 5).
+pub fn get_temperzone_status(client: &IzoneClient, output: OutputFormat) -> Result<()> {
+    let info = client.temperzone_info()?;
+    log::trace!("TemperzoneInfoV2: {:#?}", info);
+    let report = TemperzoneStatusReport::from(&info);
+
+    if output != OutputFormat::Table {
+        return print_record(output, &report);
+    }
+
+    println!("╔{}╗", "═".repeat(BOX_WIDTH));
+    print_header("TEMPERZONE UNIT STATUS");
+
+    print_header("Temperatures");
+    print_line("Outdoor coil:", temp(report.outdoor_coil_c));
+    print_line("Indoor coil:", temp(report.indoor_coil_c));
+    print_line("Ambient:", temp(report.ambient_c));
+    print_line("Suction line:", temp(report.suction_line_c));
+    print_line("Discharge line:", temp(report.discharge_line_c));
+    print_line("De-ice sensor:", temp(report.de_ice_sensor_c));
+    print_line("Evaporating:", temp(report.evaporating_c));
+    print_line("Condensing:", temp(report.condensing_c));
+    print_line("Controller board:", temp(report.controller_c));
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+
+    print_header("Refrigerant");
+    print_line("Suction superheat:", format!("{:.1} K", report.suction_superheat_k));
+    print_line("Discharge superheat:", format!("{:.1} K", report.discharge_superheat_k));
+    print_line("Suction pressure:", format!("{} kPa", report.suction_pressure_kpa));
+    print_line("Discharge pressure:", format!("{} kPa", report.discharge_pressure_kpa));
+    print_line("EXV1 position:", format!("{} steps", report.exv1_steps));
+    print_line("EXV2 position:", format!("{} steps", report.exv2_steps));
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+
+    print_header("Outputs");
+    print_line("Compressor capacity:", format!("{}%", report.capacity_pct));
+    print_line("Outdoor fan speed:", report.outdoor_fan_speed.to_string());
+    print_line("Indoor fan speed:", report.indoor_fan_speed.to_string());
+    print_line("Unit mode:", report.unit_mode.to_string());
+    print_line("Digital inputs on:", channels(&report.digital_inputs));
+    print_line("Digital outputs on:", channels(&report.digital_outputs));
+    print_line("Output status bits:", channels(&report.output_status));
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+
+    print_header("Indoor Unit");
+    print_line("Coil 1:", temp(report.indoor_coil1_c));
+    print_line("Suction line 1:", temp(report.indoor_suction_line1_c));
+    print_line("Coil 2:", temp(report.indoor_coil2_c));
+    print_line("Suction line 2:", temp(report.indoor_suction_line2_c));
+    print_line("Supply air:", temp(report.supply_air_c));
+    print_line("Return air:", temp(report.return_air_c));
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+
+    print_header("UC8 Controller");
+    print_line("ID code:", report.uc8_id_code.to_string());
+    print_line("Software version:", report.uc8_software_version.to_string());
+    print_line("Minimum on-off time:", format!("{} s", report.min_on_off_s));
+    print_line("Minimum off-on time:", format!("{} s", report.min_off_on_s));
+    print_line("Minimum on-on time:", format!("{} s", report.min_on_on_s));
+    println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
+}
+
+/// Prints the Temperzone run time, cycle, protection and fault counters.
+pub fn get_temperzone_history(client: &IzoneClient, output: OutputFormat) -> Result<()> {
+    let info = client.temperzone_info()?;
+    log::trace!("History8: {:#?}", info.history8);
+    let h = TemperzoneHistoryReport::from(&info.history8);
+
+    if output != OutputFormat::Table {
+        return print_record(output, &h);
+    }
+
+    let count = |n: u32| if n == 0 { n.to_string() } else { n.to_string().yellow().to_string() };
+    let fault = |n: u32| if n == 0 { n.to_string().green().to_string() } else { n.to_string().red().to_string() };
+
+    println!("╔{}╗", "═".repeat(BOX_WIDTH));
+    print_header("TEMPERZONE UNIT HISTORY");
+
+    print_header("Running");
+    print_line("Modbus address:", h.modbus_address.to_string());
+    print_line("Total running time:", format!("{} h {} min", h.running_hours, h.running_minutes));
+    print_line("Cooling cycles:", h.cooling_cycles.to_string());
+    print_line("Heating cycles:", h.heating_cycles.to_string());
+    print_line("De-ice cycles:", h.deice_cycles.to_string());
+    print_line("Power-on resets:", h.power_on_resets.to_string());
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+
+    print_header("Protection Events");
+    print_line("High pressure trips:", count(h.hp_trips));
+    print_line("Low pressure trips:", count(h.lp_trips));
+    print_line("Frost protection:", count(h.frost_protection_events));
+    print_line("Freeze protection:", count(h.freeze_protection_events));
+    print_line("High temperature:", count(h.high_temperature_protection_events));
+    print_line("High suction line temperature:", count(h.high_suction_line_temperature_protection_events));
+    print_line("Overload:", count(h.overload_protection_events));
+    print_line("Low discharge superheat:", count(h.low_discharge_superheat_protection_events));
+    print_line("High discharge superheat:", count(h.high_discharge_superheat_protection_events));
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+
+    print_header("Faults");
+    print_line("Indoor coil sensor:", fault(h.indoor_coil_sensor_faults));
+    print_line("Outdoor coil sensor:", fault(h.outdoor_coil_sensor_faults));
+    print_line("Outdoor ambient sensor:", fault(h.outdoor_ambient_sensor_faults));
+    print_line("Discharge line sensor:", fault(h.discharge_line_sensor_faults));
+    print_line("Suction line sensor:", fault(h.suction_line_sensor_faults));
+    print_line("De-ice sensor:", fault(h.deice_sensor_faults));
+    print_line("High pressure sensor:", fault(h.high_pressure_sensor_faults));
+    print_line("Low pressure sensor:", fault(h.low_pressure_sensor_faults));
+    print_line("High board temperature:", fault(h.high_board_temperature_faults));
+    print_line("Reverse cycle valve:", fault(h.reverse_cycle_valve_faults));
+    print_line("IUC communication:", fault(h.iuc_communication_faults));
+    print_line("IUC:", fault(h.iuc_faults));
+    print_line("Inverter:", fault(h.inverter_faults));
+    print_line("Compressor out of envelope:", fault(h.compressor_out_of_envelope_faults));
+    println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
+}
+
+/// Changes to the Temperzone unit settings; `None` keeps the current value.
+#[derive(Debug)]
+pub struct TemperzoneConfigChanges<'a> {
+    pub heat_setpoint: Option<f32>,
+    pub cool_setpoint: Option<f32>,
+    pub fan_type: Option<&'a str>,
+    pub mode_type: Option<&'a str>,
+    pub quiet: Option<bool>,
+}
+
+impl TemperzoneConfigChanges<'_> {
+    fn is_empty(&self) -> bool {
+        self.heat_setpoint.is_none()
+            && self.cool_setpoint.is_none()
+            && self.fan_type.is_none()
+            && self.mode_type.is_none()
+            && self.quiet.is_none()
+    }
+}
+
+/// Converts a °C setpoint to x100, checking it against the documented limits.
+fn setpoint_x100(label: &str, celsius: f32, min: u32, max: u32) -> Result<u32> {
+    let value = (celsius * 100.0).round();
+    if !(min as f32..=max as f32).contains(&value) {
+        return Err(IzoneError::validation(format!(
+            "{} setpoint must be {:.1}-{:.1}°C",
+            label,
+            min as f32 / 100.0,
+            max as f32 / 100.0
+        )));
+    }
+    Ok(value as u32)
+}
+
+/// Shows the Temperzone unit settings, applying any `changes` first. Changed
+/// values are read back; the setpoints and unit options are sent as pairs, so
+/// the value not given is resent unchanged.
+pub fn temperzone_config(client: &IzoneClient, changes: TemperzoneConfigChanges, output: OutputFormat) -> Result<()> {
+    let heat = changes.heat_setpoint.map(|c| setpoint_x100("Heat", c, 3000, 4000)).transpose()?;
+    let cool = changes.cool_setpoint.map(|c| setpoint_x100("Cool", c, 500, 1500)).transpose()?;
+    let fan_type = changes
+        .fan_type
+        .map(|name| {
+            get_temperzone_fan_type_value(name).ok_or_else(|| {
+                IzoneError::validation(format!("Unknown fan type '{}'.\nAvailable fan types: variable, 3-speed.", name))
+            })
+        })
+        .transpose()?;
+    let mode_type = changes
+        .mode_type
+        .map(|name| {
+            get_temperzone_mode_type_value(name).ok_or_else(|| {
+                IzoneError::validation(format!(
+                    "Unknown mode type '{}'.\nAvailable mode types: no-expansion, single-expansion, series-expansion, dry-mode.",
+                    name
+                ))
+            })
+        })
+        .transpose()?;
+
+    let mut tz = client.system()?.temperzone;
+    log::trace!("Temperzone settings: {:#?}", tz);
+
+    if !changes.is_empty() {
+        if heat.is_some() || cool.is_some() {
+            client.command(api::temperzone_setpoints_command(
+                heat.unwrap_or(tz.heat_setpoint),
+                cool.unwrap_or(tz.cool_setpoint),
+            ))?;
+        }
+        if fan_type.is_some() || mode_type.is_some() {
+            client.command(api::temperzone_unit_command(
+                fan_type.unwrap_or(tz.fan_type),
+                mode_type.unwrap_or(tz.mode_type),
+            ))?;
+        }
+        if let Some(quiet) = changes.quiet {
+            client.command(api::temperzone_quiet_command(quiet))?;
+        }
+
+        tz = client.system()?.temperzone;
+        let mismatch = [
+            ("HeatSetpoint", heat, tz.heat_setpoint),
+            ("CoolSetpoint", cool, tz.cool_setpoint),
+            ("FanType", fan_type.map(u32::from), tz.fan_type as u32),
+            ("ModeType", mode_type.map(u32::from), tz.mode_type as u32),
+            ("Quiet", changes.quiet.map(u32::from), tz.quiet as u32),
+        ]
+        .into_iter()
+        .find(|(_, wanted, actual)| wanted.is_some_and(|wanted| wanted != *actual));
+        if let Some((field, wanted, actual)) = mismatch {
+            return Err(IzoneError::Controller {
+                url: client.base_url().to_string(),
+                message: format!("Temperzone {} still reports {} after setting it to {}.", field, actual, wanted.unwrap_or_default()),
+            });
+        }
+    }
+
+    if output != OutputFormat::Table {
+        return print_record(output, &TemperzoneConfigReport::from(&tz));
+    }
+
+    let on_off = |on: bool| if on { "On".green().to_string() } else { "Off".normal().to_string() };
+
+    println!("╔{}╗", "═".repeat(BOX_WIDTH));
+    print_header(if changes.is_empty() { "TEMPERZONE UNIT SETTINGS" } else { "TEMPERZONE UNIT SETTINGS (UPDATED)" });
+    print_line("Heat mode setpoint:", temp(tz.heat_setpoint as f64 / 100.0));
+    print_line("Cool mode setpoint:", temp(tz.cool_setpoint as f64 / 100.0));
+    print_line("Fan type:", get_temperzone_fan_type_text(tz.fan_type));
+    print_line("Mode type:", get_temperzone_mode_type_text(tz.mode_type));
+    print_line("Outdoor fan quiet mode:", on_off(tz.quiet != 0));
+    println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
+}
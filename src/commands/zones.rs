@@ -1,17 +1,16 @@
 // izone/src/commands/zones.rs
 
-use reqwest::blocking::Client;
+use izone::{IzoneClient, IzoneError, Result, ZoneMap};
 use serde_json::{json, Value};
 use colored::Colorize;
-use std::process::exit;
 use std::thread; // Added for sleep functionality
-use std::time::Duration; // Added for duration specification
+use std::time::{Duration, Instant}; // Added for duration specification
 
-// Corrected imports for API functions and added get_visible_length from helpers
-use crate::api::{make_query_request, make_command_request};
-use crate::constants::{self, ZONES}; // Import the constants module itself, and ZONES
-use crate::helpers::{format_temp, get_battery_level_text, get_zone_type_text, get_visible_length, get_sensor_fault_text}; // Added get_colored_system_mode
-use crate::models::ZonesV2Response; // Removed ZoneListV2Response from here, will use full path where needed
+use izone::api;
+use izone::helpers::{format_temp, get_battery_level_text, get_zone_type_text, get_visible_length, get_sensor_fault_text, get_sensor_type_text, get_sensor_type_value, get_zone_type_value, get_rf_signal_text, get_tx_interval_value}; // Added get_colored_system_mode
+use izone::models::{ZoneMode, ZonesV2};
+use izone::report::ZoneReport;
+use crate::commands::output::{print_record, print_records, OutputFormat};
 
 // New helper function to get colored zone mode text
 fn get_colored_zone_mode(mode: u8) -> String {
@@ -25,25 +24,13 @@ fn get_colored_zone_mode(mode: u8) -> String {
     }
 }
 
-pub fn control_zone(client: &Client, zone_name: &str, action: &str, value: Option<&str>) {
-    let zone_index = match ZONES.get(zone_name) {
-        Some(&index) => index,
-        None => {
-            eprintln!(
-                "{}{}{}{}",
-                "Error: Unknown zone '".red(),
-                zone_name.red(),
-                "'.\nAvailable zones: ".red(),
-                ZONES
-                    .keys()
-                    .map(|s| s.to_string())
-                    .collect::<Vec<String>>()
-                    .join(", ")
-                    .red()
-            );
-            exit(1);
-        }
+pub fn control_zone(client: &IzoneClient, zones: &ZoneMap, zone_name: &str, action: &str, value: Option<&str>) -> Result<()> {
+    // Loose matches are fine for reading a zone, but must not change the wrong one
+    let zone_index = match action {
+        "status" | "stat" | "temp" => zones.resolve(zone_name)?,
+        _ => zones.resolve_strict(zone_name)?,
     };
+    let zone_name = zones.name_of(zone_index).unwrap_or(zone_name);
 
     let capitalized_zone_name = {
         let mut chars = zone_name.chars();
@@ -59,62 +46,55 @@ pub fn control_zone(client: &Client, zone_name: &str, action: &str, value: Optio
     // Initialize command_data directly from the match expression
     let command_data: Option<Value> = match action {
         "status" | "stat" => {
-            get_zone_status(client, zone_name);
-            return; // Exit function after displaying status, no command to send
+            return get_zone_status(client, zones, zone_name, OutputFormat::Table); // No command to send
         }
         "temp" => {
-            get_zone_temperature(client, zone_name);
-            return; // Exit function after displaying temperature, no command to send
+            return get_zone_temperature(client, zones, zone_name); // No command to send
         }
-        "open" => Some(json!({"ZoneMode":{"Index":zone_index,"Mode":1}})), // Open mode
-        "off" => Some(json!({"ZoneMode":{"Index":zone_index,"Mode":2}})), // Off mode
-        "on" | "auto" => Some(json!({"ZoneMode":{"Index":zone_index,"Mode":3}})), // Auto mode (ON)
-        "override" => Some(json!({"ZoneMode":{"Index":zone_index,"Mode":4}})), // Override mode
-        "constant" => Some(json!({"ZoneMode":{"Index":zone_index,"Mode":5}})), // Constant mode
+        "open" => Some(api::zone_mode_command(zone_index, ZoneMode::Open)), // Open mode
+        "off" => Some(api::zone_mode_command(zone_index, ZoneMode::Close)), // Off mode
+        "on" | "auto" => Some(api::zone_mode_command(zone_index, ZoneMode::Auto)), // Auto mode (ON)
+        "override" => Some(api::zone_mode_command(zone_index, ZoneMode::Override)), // Override mode
+        "constant" => Some(api::zone_mode_command(zone_index, ZoneMode::Constant)), // Constant mode
         "set_setpoint" => {
-            let setpoint_raw = value.expect("Setpoint temperature is required for set_setpoint action.");
+            let setpoint_raw = value.ok_or_else(|| IzoneError::validation("Setpoint temperature is required for set_setpoint action."))?;
             let setpoint_float: f32 = setpoint_raw
                 .parse()
-                .expect("Invalid setpoint temperature. Must be a number.");
+                .map_err(|_| IzoneError::validation("Invalid setpoint temperature. Must be a number."))?;
             let setpoint_int = (setpoint_float * 100.0).round() as u32;
 
-            if setpoint_int < 1500 || setpoint_int > 3000 {
-                eprintln!(
-                    "{}Setpoint temperature '{}' out of valid range (15.0-30.0°C).",
-                    "Error: ".red(),
+            if !(1500..=3000).contains(&setpoint_int) {
+                return Err(IzoneError::validation(format!(
+                    "Setpoint temperature '{}' out of valid range (15.0-30.0°C).",
                     setpoint_raw
-                );
-                exit(1);
+                )));
             }
-            Some(json!({"ZoneSetpoint":{"Index":zone_index,"Setpoint":setpoint_int}}))
+            Some(api::zone_setpoint_command(zone_index, setpoint_int))
         }
         "set_max_air" => {
-            let percentage_raw = value.expect("Max air percentage is required.");
+            let percentage_raw = value.ok_or_else(|| IzoneError::validation("Max air percentage is required."))?;
             let percentage: u8 = percentage_raw
                 .parse()
-                .expect("Invalid percentage. Must be a number between 0 and 100.");
+                .map_err(|_| IzoneError::validation("Invalid percentage. Must be a number between 0 and 100."))?;
             if percentage > 100 {
-                eprintln!("{}", "Error: Max air percentage cannot exceed 100.".red());
-                exit(1);
+                return Err(IzoneError::validation("Max air percentage cannot exceed 100."));
             }
             Some(json!({"ZoneAirflow":{"Index":zone_index,"MaxAir":percentage}}))
         }
         "set_min_air" => {
-            let percentage_raw = value.expect("Min air percentage is required.");
+            let percentage_raw = value.ok_or_else(|| IzoneError::validation("Min air percentage is required."))?;
             let percentage: u8 = percentage_raw
                 .parse()
-                .expect("Invalid percentage. Must be a number between 0 and 100.");
+                .map_err(|_| IzoneError::validation("Invalid percentage. Must be a number between 0 and 100."))?;
             if percentage > 100 { // Although min, still sensible to cap at 100
-                eprintln!("{}", "Error: Min air percentage cannot exceed 100.".red());
-                exit(1);
+                return Err(IzoneError::validation("Min air percentage cannot exceed 100."));
             }
             Some(json!({"ZoneAirflow":{"Index":zone_index,"MinAir":percentage}}))
         }
         "set_name" => {
-            let new_name = value.expect("New zone name is required.");
+            let new_name = value.ok_or_else(|| IzoneError::validation("New zone name is required."))?;
             if new_name.len() > 15 {
-                eprintln!("{}", "Error: Zone name cannot exceed 15 characters.".red());
-                exit(1);
+                return Err(IzoneError::validation("Zone name cannot exceed 15 characters."));
             }
             Some(json!({"ZoneName":{"Index":zone_index,"Name":new_name}}))
         }
@@ -125,26 +105,21 @@ pub fn control_zone(client: &Client, zone_name: &str, action: &str, value: Optio
             None
         }
         _ => {
-            eprintln!(
-                "{}{}{}",
-                "Error: Unknown zone action '".red(),
-                action.red(),
-                "'.\nAvailable actions for zones: status, temp, on, off, open, auto, override, constant, set-setpoint, set-max-air, set-min-air, set-name, summary.".red()
-            );
-            exit(1); // Exit if unknown action, no command_data to return
+            return Err(IzoneError::validation(format!(
+                "Unknown zone action '{}'.\nAvailable actions for zones: status, temp, on, off, open, auto, override, constant, set-setpoint, set-max-air, set-min-air, set-name, summary.",
+                action
+            )));
         }
     };
 
     // Only make a command request if command_data is Some
     if let Some(cmd_data) = command_data {
-        make_command_request(client, cmd_data.clone()) // Use .clone() to allow sending the same data twice
-            .expect(&format!("Failed to execute '{}' for zone '{}'", action, zone_name));
+        client.command(cmd_data.clone())?; // Use .clone() to allow sending the same data twice
 
         // If the action is "set_setpoint", call the API a second time after a delay
         if action == "set_setpoint" {
             thread::sleep(Duration::from_millis(100)); // 0.1 second delay
-            make_command_request(client, cmd_data)
-                .expect(&format!("Failed to re-execute '{}' for zone '{}' after delay", action, zone_name));
+            client.command(cmd_data)?;
         }
 
         println!("╔{}╗", "═".repeat(BOX_WIDTH));
@@ -177,34 +152,32 @@ pub fn control_zone(client: &Client, zone_name: &str, action: &str, value: Optio
         // Adjusted padding for messages in control_zone to fix off-by-one alignment
         let visible_message_length = get_visible_length(&message);
         let spaces_needed = PADDING_WIDTH - visible_message_length;
-        println!("║ {}{}{} ║", message, " ".repeat(spaces_needed), "");
+        println!("║ {}{} ║", message, " ".repeat(spaces_needed));
         println!("╚{}╝", "═".repeat(BOX_WIDTH));
     }
+
+    Ok(())
 }
 
 
-pub fn get_zone_status(client: &Client, zone_name: &str) {
-    let zone_index = match constants::ZONES.get(zone_name) {
-        Some(&index) => index,
-        None => {
-            eprintln!(
-                "{}{}{}{}",
-                "Error: Unknown zone '".red(),
-                zone_name.red(),
-                "'.\nAvailable zones: ".red(),
-                constants::ZONES
-                    .keys()
-                    .map(|s| s.to_string())
-                    .collect::<Vec<String>>()
-                    .join(", ")
-                    .red()
-            );
-            exit(1);
-        }
-    };
+/// Reads a zone for display, reusing the record the zone map discovered in
+/// this invocation instead of querying it again.
+fn read_zone(client: &IzoneClient, zones: &ZoneMap, zone_index: u8) -> Result<ZonesV2> {
+    match zones.zone(zone_index) {
+        Some(zone) => Ok(zone.clone()),
+        None => client.zone(zone_index),
+    }
+}
+
+pub fn get_zone_status(client: &IzoneClient, zones: &ZoneMap, zone_name: &str, output: OutputFormat) -> Result<()> {
+    let zone_index = zones.resolve(zone_name)?;
+    let zone_name = zones.name_of(zone_index).unwrap_or(zone_name);
 
-    // Changed Type from 3 to 2 for zone status queries
-    let query_data = json!({ "iZoneV2Request": { "Type": 2, "No": zone_index, "No1": 0 } });
+    if output != OutputFormat::Table {
+        let mut zone = read_zone(client, zones, zone_index)?;
+        zone.index = zone_index;
+        return print_record(output, &ZoneReport::from(&zone));
+    }
 
     const BOX_WIDTH: usize = 60; // Slightly wider box for detailed zone status
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
@@ -214,18 +187,7 @@ pub fn get_zone_status(client: &Client, zone_name: &str) {
     println!("║ {:^padding_width$} ║", format!("ZONE STATUS: {}", zone_name.to_uppercase()), padding_width = PADDING_WIDTH);
     println!("╠{}╣", "═".repeat(BOX_WIDTH));
 
-    let response_value = match make_query_request(client, query_data) {
-        Ok(val) => val,
-        Err(e) => {
-            eprintln!("{}", format!("Error querying zone status: {}", e).red());
-            exit(1);
-        }
-    };
-
-    let zone_response: ZonesV2Response = serde_json::from_value(response_value.clone())
-        .expect("Failed to parse zone status response");
-
-    let zone = zone_response.zones_v2;
+    let zone = read_zone(client, zones, zone_index)?;
 
     // Changed to use the new get_colored_zone_mode for zone status display
     let mode_text = get_colored_zone_mode(zone.mode);
@@ -259,7 +221,7 @@ pub fn get_zone_status(client: &Client, zone_name: &str) {
     print_line("Setpoint:", format!("{}°C", setpoint_text).normal().to_string());
     print_line("Damper Position:", damper_pos_text.normal().to_string());
     print_line("Zone Type:", zone_type_text.normal().to_string());
-    print_line("Sensor Type:", zone.sens_type.to_string().normal().to_string());
+    print_line("Sensor Type:", get_sensor_type_text(zone.sens_type).normal().to_string());
     print_line("Max Air:", format!("{}%", zone.max_air).normal().to_string());
     print_line("Min Air:", format!("{}%", zone.min_air).normal().to_string());
     print_line("Constant:", zone.constant.to_string().normal().to_string());
@@ -280,50 +242,21 @@ pub fn get_zone_status(client: &Client, zone_name: &str) {
 
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
 
-    unsafe {
-        if constants::VERBOSE {
-            println!("Full ZonesV2Response: {:#?}", response_value);
-        }
-    }
-}
+    log::trace!("Full ZonesV2Response: {:#?}", zone);
 
-pub fn get_zone_temperature(client: &Client, zone_name: &str) {
-    let zone_index = match constants::ZONES.get(zone_name) {
-        Some(&index) => index,
-        None => {
-            eprintln!(
-                "{}{}{}{}",
-                "Error: Unknown zone '".red(),
-                zone_name.red(),
-                "'.\nAvailable zones: ".red(),
-                constants::ZONES
-                    .keys()
-                    .map(|s| s.to_string())
-                    .collect::<Vec<String>>()
-                    .join(", ")
-                    .red()
-            );
-            exit(1);
-        }
-    };
+    Ok(())
+}
 
-    let query_data = json!({ "iZoneV2Request": { "Type": 2, "No": zone_index, "No1": 0 } });
+pub fn get_zone_temperature(client: &IzoneClient, zones: &ZoneMap, zone_name: &str) -> Result<()> {
+    let zone_index = zones.resolve(zone_name)?;
+    let zone_name = zones.name_of(zone_index).unwrap_or(zone_name);
 
     const BOX_WIDTH_TEMP: usize = 56; // Adjust based on example
     const PADDING_WIDTH_TEMP: usize = BOX_WIDTH_TEMP - 2;
 
-    let response_value = match make_query_request(client, query_data) {
-        Ok(val) => val,
-        Err(e) => {
-            eprintln!("{}", format!("Error querying zone temperature: {}", e).red());
-            exit(1);
-        }
-    };
-
-    let zone_response: ZonesV2Response = serde_json::from_value(response_value)
-        .expect("Failed to parse zone temperature response");
+    let zone = read_zone(client, zones, zone_index)?;
 
-    let temp_text = format_temp(zone_response.zones_v2.temp);
+    let temp_text = format_temp(zone.temp);
 
     // Capitalize the first letter of zone_name for the header, and keep the rest as is
     let capitalized_zone_name_for_header = {
@@ -343,33 +276,28 @@ pub fn get_zone_temperature(client: &Client, zone_name: &str) {
     // Corrected the named argument for padding_width
     let visible_message_length = get_visible_length(&message);
     let spaces_needed = PADDING_WIDTH_TEMP - visible_message_length;
-    println!("║ {}{}{} ║", message, " ".repeat(spaces_needed), "");
+    println!("║ {}{} ║", message, " ".repeat(spaces_needed));
     println!("╚{}╝", "═".repeat(BOX_WIDTH_TEMP));
+
+    Ok(())
 }
 
 
 // Zone configuration commands
 
-pub fn set_zone_balance_max(client: &Client, zone_name: &str, max: u8) {
-    let zone_index = match ZONES.get(zone_name) {
-        Some(&index) => index,
-        None => {
-            eprintln!("{}", format!("Error: Unknown zone '{}'", zone_name).red());
-            exit(1);
-        }
-    };
+pub fn set_zone_balance_max(client: &IzoneClient, zones: &ZoneMap, zone_name: &str, max: u8) -> Result<()> {
+    let zone_index = zones.resolve_strict(zone_name)?;
+    let zone_name = zones.name_of(zone_index).unwrap_or(zone_name);
 
-    if max > 100 || max % 5 != 0 {
-        eprintln!("{}", "Error: BalanceMax must be 0-100 in steps of 5%".red());
-        exit(1);
+    if max > 100 || !max.is_multiple_of(5) {
+        return Err(IzoneError::validation("BalanceMax must be 0-100 in steps of 5%"));
     }
 
     const BOX_WIDTH: usize = 70;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let command_data = json!({"BalanceMax": {"Index": zone_index, "Max": max}});
-    make_command_request(client, command_data)
-        .expect(&format!("Failed to set BalanceMax for zone '{}'", zone_name));
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Zone Configuration", padding_width = PADDING_WIDTH);
@@ -377,30 +305,25 @@ pub fn set_zone_balance_max(client: &Client, zone_name: &str, max: u8) {
     let message = format!("Zone '{}' BalanceMax set to {}%.", zone_name.green(), max.to_string().green());
     let visible_message_length = get_visible_length(&message);
     let spaces_needed = PADDING_WIDTH - visible_message_length;
-    println!("║ {}{}{} ║", message, " ".repeat(spaces_needed), "");
+    println!("║ {}{} ║", message, " ".repeat(spaces_needed));
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_zone_balance_min(client: &Client, zone_name: &str, min: u8) {
-    let zone_index = match ZONES.get(zone_name) {
-        Some(&index) => index,
-        None => {
-            eprintln!("{}", format!("Error: Unknown zone '{}'", zone_name).red());
-            exit(1);
-        }
-    };
+pub fn set_zone_balance_min(client: &IzoneClient, zones: &ZoneMap, zone_name: &str, min: u8) -> Result<()> {
+    let zone_index = zones.resolve_strict(zone_name)?;
+    let zone_name = zones.name_of(zone_index).unwrap_or(zone_name);
 
-    if min > 100 || min % 5 != 0 {
-        eprintln!("{}", "Error: BalanceMin must be 0-100 in steps of 5%".red());
-        exit(1);
+    if min > 100 || !min.is_multiple_of(5) {
+        return Err(IzoneError::validation("BalanceMin must be 0-100 in steps of 5%"));
     }
 
     const BOX_WIDTH: usize = 70;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let command_data = json!({"BalanceMin": {"Index": zone_index, "Min": min}});
-    make_command_request(client, command_data)
-        .expect(&format!("Failed to set BalanceMin for zone '{}'", zone_name));
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Zone Configuration", padding_width = PADDING_WIDTH);
@@ -408,26 +331,22 @@ pub fn set_zone_balance_min(client: &Client, zone_name: &str, min: u8) {
     let message = format!("Zone '{}' BalanceMin set to {}%.", zone_name.green(), min.to_string().green());
     let visible_message_length = get_visible_length(&message);
     let spaces_needed = PADDING_WIDTH - visible_message_length;
-    println!("║ {}{}{} ║", message, " ".repeat(spaces_needed), "");
+    println!("║ {}{} ║", message, " ".repeat(spaces_needed));
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_zone_damper_skip(client: &Client, zone_name: &str, skip: bool) {
-    let zone_index = match ZONES.get(zone_name) {
-        Some(&index) => index,
-        None => {
-            eprintln!("{}", format!("Error: Unknown zone '{}'", zone_name).red());
-            exit(1);
-        }
-    };
+pub fn set_zone_damper_skip(client: &IzoneClient, zones: &ZoneMap, zone_name: &str, skip: bool) -> Result<()> {
+    let zone_index = zones.resolve_strict(zone_name)?;
+    let zone_name = zones.name_of(zone_index).unwrap_or(zone_name);
 
     const BOX_WIDTH: usize = 70;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let skip_val = if skip { 1 } else { 0 };
     let command_data = json!({"DamperSkip": {"Index": zone_index, "Skip": skip_val}});
-    make_command_request(client, command_data)
-        .expect(&format!("Failed to set DamperSkip for zone '{}'", zone_name));
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Zone Configuration", padding_width = PADDING_WIDTH);
@@ -436,30 +355,25 @@ pub fn set_zone_damper_skip(client: &Client, zone_name: &str, skip: bool) {
     let message = format!("Zone '{}' DamperSkip {}.", zone_name.green(), skip_text);
     let visible_message_length = get_visible_length(&message);
     let spaces_needed = PADDING_WIDTH - visible_message_length;
-    println!("║ {}{}{} ║", message, " ".repeat(spaces_needed), "");
+    println!("║ {}{} ║", message, " ".repeat(spaces_needed));
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_zone_sensor_calibration(client: &Client, zone_name: &str, calibrate: i8) {
-    let zone_index = match ZONES.get(zone_name) {
-        Some(&index) => index,
-        None => {
-            eprintln!("{}", format!("Error: Unknown zone '{}'", zone_name).red());
-            exit(1);
-        }
-    };
+pub fn set_zone_sensor_calibration(client: &IzoneClient, zones: &ZoneMap, zone_name: &str, calibrate: i8) -> Result<()> {
+    let zone_index = zones.resolve_strict(zone_name)?;
+    let zone_name = zones.name_of(zone_index).unwrap_or(zone_name);
 
-    if calibrate < -50 || calibrate > 50 {
-        eprintln!("{}", "Error: Calibration must be between -5.0°C and +5.0°C (-50 to 50)".red());
-        exit(1);
+    if !(-50..=50).contains(&calibrate) {
+        return Err(IzoneError::validation("Calibration must be between -5.0°C and +5.0°C (-50 to 50)"));
     }
 
     const BOX_WIDTH: usize = 70;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let command_data = json!({"SensorCalib": {"Index": zone_index, "Calibrate": calibrate}});
-    make_command_request(client, command_data)
-        .expect(&format!("Failed to set sensor calibration for zone '{}'", zone_name));
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Zone Configuration", padding_width = PADDING_WIDTH);
@@ -468,26 +382,22 @@ pub fn set_zone_sensor_calibration(client: &Client, zone_name: &str, calibrate:
     let message = format!("Zone '{}' sensor calibration set to {}°C.", zone_name.green(), format!("{:+.1}", calibrate_val).green());
     let visible_message_length = get_visible_length(&message);
     let spaces_needed = PADDING_WIDTH - visible_message_length;
-    println!("║ {}{}{} ║", message, " ".repeat(spaces_needed), "");
+    println!("║ {}{} ║", message, " ".repeat(spaces_needed));
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_zone_bypass(client: &Client, zone_name: &str, bypass: bool) {
-    let zone_index = match ZONES.get(zone_name) {
-        Some(&index) => index,
-        None => {
-            eprintln!("{}", format!("Error: Unknown zone '{}'", zone_name).red());
-            exit(1);
-        }
-    };
+pub fn set_zone_bypass(client: &IzoneClient, zones: &ZoneMap, zone_name: &str, bypass: bool) -> Result<()> {
+    let zone_index = zones.resolve_strict(zone_name)?;
+    let zone_name = zones.name_of(zone_index).unwrap_or(zone_name);
 
     const BOX_WIDTH: usize = 70;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let bypass_val = if bypass { 1 } else { 0 };
     let command_data = json!({"ZoneBypass": {"Index": zone_index, "Bypass": bypass_val}});
-    make_command_request(client, command_data)
-        .expect(&format!("Failed to set bypass for zone '{}'", zone_name));
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Zone Configuration", padding_width = PADDING_WIDTH);
@@ -496,30 +406,25 @@ pub fn set_zone_bypass(client: &Client, zone_name: &str, bypass: bool) {
     let message = format!("Zone '{}' bypass {}.", zone_name.green(), bypass_text);
     let visible_message_length = get_visible_length(&message);
     let spaces_needed = PADDING_WIDTH - visible_message_length;
-    println!("║ {}{}{} ║", message, " ".repeat(spaces_needed), "");
+    println!("║ {}{} ║", message, " ".repeat(spaces_needed));
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn set_zone_area(client: &Client, zone_name: &str, area: u8) {
-    let zone_index = match ZONES.get(zone_name) {
-        Some(&index) => index,
-        None => {
-            eprintln!("{}", format!("Error: Unknown zone '{}'", zone_name).red());
-            exit(1);
-        }
-    };
+pub fn set_zone_area(client: &IzoneClient, zones: &ZoneMap, zone_name: &str, area: u8) -> Result<()> {
+    let zone_index = zones.resolve_strict(zone_name)?;
+    let zone_name = zones.name_of(zone_index).unwrap_or(zone_name);
 
     if area < 1 {
-        eprintln!("{}", "Error: Area must be at least 1 m²".red());
-        exit(1);
+        return Err(IzoneError::validation("Area must be at least 1 m²"));
     }
 
     const BOX_WIDTH: usize = 70;
     const PADDING_WIDTH: usize = BOX_WIDTH - 2;
 
     let command_data = json!({"ZoneArea": {"Index": zone_index, "Area": area}});
-    make_command_request(client, command_data)
-        .expect(&format!("Failed to set area for zone '{}'", zone_name));
+    client.command(command_data)?;
 
     println!("╔{}╗", "═".repeat(BOX_WIDTH));
     println!("║ {:^padding_width$} ║", "Zone Configuration", padding_width = PADDING_WIDTH);
@@ -527,11 +432,224 @@ pub fn set_zone_area(client: &Client, zone_name: &str, area: u8) {
     let message = format!("Zone '{}' area set to {} m².", zone_name.green(), area.to_string().green());
     let visible_message_length = get_visible_length(&message);
     let spaces_needed = PADDING_WIDTH - visible_message_length;
-    println!("║ {}{}{} ║", message, " ".repeat(spaces_needed), "");
+    println!("║ {}{} ║", message, " ".repeat(spaces_needed));
+    println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
+}
+
+/// Changes a zone's control type and sensor (`ZoneSetting`) and reads the zone
+/// back to confirm the controller applied it.
+pub fn set_zone_type(
+    client: &IzoneClient,
+    zones: &ZoneMap,
+    zone_name: &str,
+    zone_type: &str,
+    constant_no: Option<u8>,
+    sensor: &str,
+) -> Result<()> {
+    let zone_index = zones.resolve_strict(zone_name)?;
+    let zone_name = zones.name_of(zone_index).unwrap_or(zone_name);
+
+    let type_value = get_zone_type_value(zone_type).ok_or_else(|| {
+        IzoneError::validation(format!(
+            "Unknown zone type '{}'.\nAvailable types: open-close, constant, auto.",
+            zone_type
+        ))
+    })?;
+    let sensor_value = get_sensor_type_value(sensor).ok_or_else(|| {
+        IzoneError::validation(format!(
+            "Unknown sensor '{}'.\nAvailable sensors: ccts, csm, czco, crfs, cs, none.",
+            sensor
+        ))
+    })?;
+
+    // ZoneType_Constant needs a constant number within the system's NoOfConst
+    let const_no = if type_value == 2 {
+        let no_of_const = client.system()?.no_of_const;
+        if no_of_const == 0 {
+            return Err(IzoneError::validation("This system has no constants configured (NoOfConst = 0)."));
+        }
+        match constant_no {
+            Some(n) if (1..=no_of_const).contains(&n) => n,
+            _ => {
+                return Err(IzoneError::validation(format!(
+                    "A constant zone needs --constant-no between 1 and {} (NoOfConst = {}).",
+                    no_of_const, no_of_const
+                )));
+            }
+        }
+    } else {
+        if constant_no.is_some() {
+            return Err(IzoneError::validation("--constant-no only applies to constant zones."));
+        }
+        0
+    };
+
+    let before = client.zone(zone_index)?;
+    client.command(api::zone_setting_command(zone_index, sensor_value, type_value, const_no))?;
+    let after = client.zone(zone_index)?;
+
+    if after.zone_type != type_value || after.sens_type != sensor_value || (type_value == 2 && after.constant != const_no) {
+        return Err(IzoneError::Controller {
+            url: client.base_url().to_string(),
+            message: format!(
+                "Zone '{}' still reports {} / {} sensor after ZoneSetting; the controller did not apply the change.",
+                zone_name,
+                get_zone_type_text(after.zone_type),
+                get_sensor_type_text(after.sens_type)
+            ),
+        });
+    }
+
+    const BOX_WIDTH: usize = 70;
+    const PADDING_WIDTH: usize = BOX_WIDTH - 2;
+
+    let describe = |zone_type: u8, sensor: u8, constant: u8| {
+        let mut text = format!("{} / {}", get_zone_type_text(zone_type), get_sensor_type_text(sensor));
+        if zone_type == 2 {
+            text.push_str(&format!(" / constant {}", constant));
+        }
+        text
+    };
+
+    println!("╔{}╗", "═".repeat(BOX_WIDTH));
+    println!("║ {:^padding_width$} ║", "Zone Configuration", padding_width = PADDING_WIDTH);
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+    for message in [
+        format!("Zone '{}' reconfigured.", zone_name.green()),
+        format!("Before: {}", describe(before.zone_type, before.sens_type, before.constant)),
+        format!("After:  {}", describe(after.zone_type, after.sens_type, after.constant).green()),
+    ] {
+        let spaces_needed = PADDING_WIDTH.saturating_sub(get_visible_length(&message));
+        println!("║ {}{} ║", message, " ".repeat(spaces_needed));
+    }
+    println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
+}
+
+/// How often the zone is re-read while waiting for a paired sensor.
+const PAIR_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Puts the controller into sensor pairing mode, then polls the zone until its
+/// sensor reports in (no sensor fault and an RF signal) or `wait_secs` expires.
+/// `RfPair` is not zone specific; `Crfs2SensorPair` pairs to this zone.
+///
+/// A zone whose sensor already reports in needs `force`; the new sensor is
+/// then only recognised by a changed RF signal or battery level.
+pub fn pair_zone_sensor(
+    client: &IzoneClient,
+    zones: &ZoneMap,
+    zone_name: &str,
+    crfs2: bool,
+    tx_interval: Option<u16>,
+    wait_secs: u64,
+    force: bool,
+) -> Result<()> {
+    let zone_index = zones.resolve_strict(zone_name)?;
+    let zone_name = zones.name_of(zone_index).unwrap_or(zone_name);
+
+    let command_data = if crfs2 {
+        let interval = match tx_interval {
+            Some(seconds) => get_tx_interval_value(seconds).ok_or_else(|| {
+                IzoneError::validation(format!(
+                    "Invalid transmit interval {}s.\nAvailable intervals: 2, 4, 8, 16, 32, 64, 128, 256 seconds.",
+                    seconds
+                ))
+            })?,
+            None => 0,
+        };
+        api::crfs2_sensor_pair_command(zone_index, interval)
+    } else {
+        if tx_interval.is_some() {
+            return Err(IzoneError::validation("--tx-interval only applies to CRFS2 sensors (add --crfs2)."));
+        }
+        api::rf_pair_command()
+    };
+
+    let reporting = |zone: &ZonesV2| zone.sensor_fault == 0 && zone.rf_signal != 3;
+
+    let before = client.zone(zone_index)?;
+    let already_reporting = reporting(&before);
+    if already_reporting {
+        if !force {
+            return Err(IzoneError::validation(format!(
+                "Zone '{}' already has a sensor reporting in (RF signal {}), so a new sensor could not be told apart.\nAdd --force to pair anyway.",
+                zone_name,
+                get_rf_signal_text(before.rf_signal)
+            )));
+        }
+        log::warn!(
+            "Zone '{}' already has a sensor reporting in; waiting for its RF signal or battery level to change.",
+            zone_name
+        );
+    }
+    // Only a change from `before` shows that a sensor paired, not the old one still reporting
+    let paired = |zone: &ZonesV2| {
+        reporting(zone)
+            && (!already_reporting || zone.rf_signal != before.rf_signal || zone.batt_volt != before.batt_volt)
+    };
+
+    client.command(command_data)?;
+    println!("Pairing mode started. Press the pair button on the sensor for zone '{}'...", zone_name.green());
+
+    let deadline = Instant::now() + Duration::from_secs(wait_secs);
+    let after = loop {
+        thread::sleep(PAIR_POLL_INTERVAL);
+        let zone = client.zone(zone_index)?;
+        log::debug!("Zone {}: rf_signal={} sensor_fault={}", zone_index, zone.rf_signal, zone.sensor_fault);
+        if paired(&zone) {
+            break zone;
+        }
+        if Instant::now() >= deadline {
+            return Err(IzoneError::Controller {
+                url: client.base_url().to_string(),
+                message: format!(
+                    "No sensor reported in for zone '{}' within {}s (RF signal {}, sensor {}).",
+                    zone_name,
+                    wait_secs,
+                    get_rf_signal_text(zone.rf_signal),
+                    get_sensor_fault_text(zone.sensor_fault)
+                ),
+            });
+        }
+    };
+
+    const BOX_WIDTH: usize = 50;
+    const PADDING_WIDTH: usize = BOX_WIDTH - 2;
+
+    println!("╔{}╗", "═".repeat(BOX_WIDTH));
+    println!("║ {:^padding_width$} ║", "Sensor Pairing", padding_width = PADDING_WIDTH);
+    println!("╠{}╣", "═".repeat(BOX_WIDTH));
+    for message in [
+        format!("Sensor for zone '{}' reported in.", zone_name.green()),
+        format!("RF Signal: {}", get_rf_signal_text(after.rf_signal)),
+        format!("Sensor Status: {}", get_sensor_fault_text(after.sensor_fault)),
+        format!("Battery Level: {}", get_battery_level_text(after.batt_volt)),
+    ] {
+        let spaces_needed = PADDING_WIDTH.saturating_sub(get_visible_length(&message));
+        println!("║ {}{} ║", message, " ".repeat(spaces_needed));
+    }
     println!("╚{}╝", "═".repeat(BOX_WIDTH));
+
+    Ok(())
 }
 
-pub fn get_all_zones_summary(client: &Client) {
+pub fn get_all_zones_summary(client: &IzoneClient, zones: &ZoneMap, output: OutputFormat) -> Result<()> {
+    if output != OutputFormat::Table {
+        let mut reports = Vec::new();
+        for zone_index in zones.indices() {
+            match read_zone(client, zones, zone_index) {
+                Ok(mut zone) => {
+                    zone.index = zone_index;
+                    reports.push(ZoneReport::from(&zone));
+                }
+                Err(e) => log::warn!("Skipping zone {}: {}", zone_index, e),
+            }
+        }
+        return print_records(output, &reports);
+    }
 
 
     // Column widths for the summary table (visible characters)
@@ -606,79 +724,23 @@ pub fn get_all_zones_summary(client: &Client) {
     // Fetch all zone data first
 
 
-    let mut zones_data: Vec<crate::models::ZonesV2> = Vec::new(); // Store ZonesV2 structs
-
-
-
-
+    let mut zones_data: Vec<izone::models::ZonesV2> = Vec::new(); // Store ZonesV2 structs
 
-    for (&zone_index, zone_name) in ZONES.values().zip(ZONES.keys()) {
 
 
-        let query_data = json!({ "iZoneV2Request": { "Type": 2, "No": zone_index, "No1": 0 } });
 
 
+    for zone_index in zones.indices() {
+        let zone_name = zones.name_of(zone_index).map(str::to_string).unwrap_or_else(|| format!("zone {}", zone_index));
 
 
-
-        // Using make_query_request and handling its Result
-
-
-        let response_value = match make_query_request(client, query_data) {
-
-
-            Ok(val) => val,
-
-
+        match read_zone(client, zones, zone_index) {
+            Ok(zone) => zones_data.push(zone),
             Err(e) => {
-
-
                 let error_message = format!("{:<NAME_COL_WIDTH$} ERROR: {}", zone_name, e);
-
-
                 println!("║ {:<width$} ║", error_message.red(), width = total_width + get_visible_length(&error_message.red().to_string()) - error_message.len());
-
-
-                continue;
-
-
             }
-
-
-        };
-
-
-
-
-
-        let zones_v2_response: ZonesV2Response =
-
-
-            match serde_json::from_value(response_value.clone()) {
-
-
-                Ok(z) => z,
-
-
-                Err(e) => {
-
-
-                    let error_message = format!("{:<NAME_COL_WIDTH$} ERROR: {}", zone_name, e);
-
-
-                    println!("║ {:<width$} ║", error_message.red(), width = total_width + get_visible_length(&error_message.red().to_string()) - error_message.len());
-
-
-                    continue;
-
-
-                }
-
-
-            };
-
-
-        zones_data.push(zones_v2_response.zones_v2);
+        }
 
 
     }
@@ -834,25 +896,12 @@ pub fn get_all_zones_summary(client: &Client) {
     println!("╚{}╝", "═".repeat(total_width + 2));
 
 
-    unsafe {
-
-
-        if constants::VERBOSE {
-
-
-            // Reconstruct all_zones_responses to ensure sorted output if VERBOSE is true
-
-
-            let sorted_all_zones_responses: Vec<Value> = zones_data.iter().map(|z| serde_json::to_value(&z).unwrap()).collect();
-
-
-            println!("{}", serde_json::to_string_pretty(&sorted_all_zones_responses).unwrap());
-
-
-        }
-
-
+    if log::log_enabled!(log::Level::Trace) {
+        // Dump the zones in the same (sorted) order as the table
+        let sorted_all_zones_responses: Vec<Value> = zones_data.iter().map(|z| serde_json::to_value(z).unwrap()).collect();
+        log::trace!("{}", serde_json::to_string_pretty(&sorted_all_zones_responses).unwrap_or_default());
     }
 
 
+    Ok(())
 }
\ No newline at end of file
@@ -1,79 +1,342 @@
 // izone/src/constants.rs
 
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use serde::Deserialize;
 
+use crate::client::ClientOptions;
+use crate::discovery;
+
 // Default IP address (fallback if no config file exists)
 pub const DEFAULT_IZONE_IP: &str = "http://192.168.1.130";
 pub const QUERY_URL_SUFFIX: &str = "/iZoneRequestV2";
 pub const COMMAND_URL_SUFFIX: &str = "/iZoneCommandV2";
 
+// Request defaults (overridable in the config file and on the command line)
+pub const DEFAULT_TIMEOUT_MS: u64 = 5000;
+pub const DEFAULT_RETRIES: u32 = 2;
+pub const DEFAULT_RETRY_BACKOFF_MS: u64 = 500;
+
+// Largest zone count an iZone system supports
+pub const MAX_ZONES: u8 = 14;
+
 // Config file structure
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Default, Clone)]
 pub struct Config {
     pub izone_ip: Option<String>,
+    /// Per-request timeout in milliseconds.
+    pub timeout_ms: Option<u64>,
+    /// How many times a failed query (or idempotent command) is retried.
+    pub retries: Option<u32>,
+    /// Delay before the first retry in milliseconds, doubled for each further retry.
+    pub retry_backoff_ms: Option<u64>,
+    /// Zone name to index table; zones are discovered from the controller when absent.
+    pub zones: Option<BTreeMap<String, u8>>,
+    /// Default `--output` format (table, json, yaml or csv).
+    pub output: Option<String>,
+    /// Profile used when neither `--profile` nor `IZONE_PROFILE` is given.
+    pub default_profile: Option<String>,
+    /// Named controllers, `[profiles.<name>]`.
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+    /// The profile applied by [`Config::select_profile`], if any.
+    #[serde(skip)]
+    pub active_profile: Option<String>,
+}
+
+/// One `[profiles.<name>]` table. Keys left out fall back to the top level,
+/// except `zones`, which are discovered when the profile has none.
+#[derive(Deserialize, Debug, Default, Clone)]
+pub struct Profile {
+    pub izone_ip: Option<String>,
+    pub timeout_ms: Option<u64>,
+    pub retries: Option<u32>,
+    pub retry_backoff_ms: Option<u64>,
+    pub zones: Option<BTreeMap<String, u8>>,
+    pub output: Option<String>,
+}
+
+impl Config {
+    /// Applies the named profile (or `default_profile` when `name` is `None`)
+    /// over the top-level settings.
+    pub fn select_profile(mut self, name: Option<&str>) -> crate::Result<Config> {
+        let Some(name) = name.map(str::to_string).or_else(|| self.default_profile.clone()) else {
+            return Ok(self);
+        };
+        let profile = self.profiles.get(&name).cloned().ok_or_else(|| {
+            let names: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            crate::IzoneError::validation(format!(
+                "Unknown profile '{}'.\nAvailable profiles: {}",
+                name,
+                if names.is_empty() { "none (add a [profiles.<name>] table to your config)".to_string() } else { names.join(", ") }
+            ))
+        })?;
+
+        self.izone_ip = profile.izone_ip.or(self.izone_ip);
+        self.timeout_ms = profile.timeout_ms.or(self.timeout_ms);
+        self.retries = profile.retries.or(self.retries);
+        self.retry_backoff_ms = profile.retry_backoff_ms.or(self.retry_backoff_ms);
+        // Zone names belong to one controller, so they are never inherited
+        self.zones = profile.zones;
+        self.output = profile.output.or(self.output);
+        self.active_profile = Some(name);
+        Ok(self)
+    }
+
+    /// Controller URL, falling back to the default IP.
+    pub fn izone_ip(&self) -> String {
+        self.izone_ip.clone().unwrap_or_else(|| DEFAULT_IZONE_IP.to_string())
+    }
+
+    /// Whether `izone_ip = "auto"`, i.e. the bridge is found by discovery.
+    pub fn izone_ip_is_auto(&self) -> bool {
+        self.izone_ip.as_deref().is_some_and(|ip| ip.eq_ignore_ascii_case("auto"))
+    }
+
+    /// Controller URL, resolving `"auto"` through the discovery cache or a broadcast.
+    pub fn resolve_izone_ip(&self) -> crate::Result<String> {
+        if self.izone_ip_is_auto() {
+            discovery::auto_bridge_url(
+                self.active_profile.as_deref(),
+                Duration::from_millis(discovery::DEFAULT_DISCOVERY_WAIT_MS),
+            )
+        } else {
+            Ok(self.izone_ip())
+        }
+    }
+
+    /// Broadcasts for the `"auto"` bridge again, replacing the cached one.
+    pub fn rediscover_izone_ip(&self) -> crate::Result<String> {
+        discovery::rediscover_bridge_url(
+            self.active_profile.as_deref(),
+            Duration::from_millis(discovery::DEFAULT_DISCOVERY_WAIT_MS),
+        )
+    }
+
+    /// Client options from the configured timeout and retry settings.
+    pub fn client_options(&self) -> ClientOptions {
+        ClientOptions {
+            timeout: Some(Duration::from_millis(self.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS))),
+            retries: self.retries.unwrap_or(DEFAULT_RETRIES),
+            retry_backoff: Duration::from_millis(self.retry_backoff_ms.unwrap_or(DEFAULT_RETRY_BACKOFF_MS)),
+            ..ClientOptions::default()
+        }
+    }
+}
+
+/// Values accepted for the `output` key.
+pub const OUTPUT_FORMATS: &[&str] = &["table", "json", "yaml", "csv"];
+
+/// Environment variables that override config keys, applied after the profile.
+pub const ENV_OVERRIDES: &[(&str, &str)] = &[
+    ("IZONE_IP", "izone_ip"),
+    ("IZONE_TIMEOUT_MS", "timeout_ms"),
+    ("IZONE_RETRIES", "retries"),
+    ("IZONE_RETRY_BACKOFF_MS", "retry_backoff_ms"),
+    ("IZONE_OUTPUT", "output"),
+];
+
+impl Config {
+    /// Applies the `ENV_OVERRIDES` variables that are set, returning their names.
+    pub fn apply_env(&mut self) -> crate::Result<Vec<&'static str>> {
+        fn number<T: std::str::FromStr>(var: &str, value: &str) -> crate::Result<T> {
+            value.trim().parse().map_err(|_| {
+                crate::IzoneError::validation(format!("{}='{}' is not a valid number.", var, value))
+            })
+        }
+
+        let mut applied = Vec::new();
+        for &(var, key) in ENV_OVERRIDES {
+            let Ok(value) = std::env::var(var) else { continue };
+            if value.is_empty() {
+                continue;
+            }
+            match key {
+                "izone_ip" => self.izone_ip = Some(value),
+                "timeout_ms" => self.timeout_ms = Some(number(var, &value)?),
+                "retries" => self.retries = Some(number(var, &value)?),
+                "retry_backoff_ms" => self.retry_backoff_ms = Some(number(var, &value)?),
+                _ => self.output = Some(value),
+            }
+            applied.push(var);
+        }
+        Ok(applied)
+    }
+}
+
+/// A problem found in a config file, with its line when known.
+#[derive(Debug, Clone)]
+pub struct ConfigIssue {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// A config and the file it was read from.
+#[derive(Debug, Default)]
+pub struct LoadedConfig {
+    pub config: Config,
+    /// `None` when no config file was found and the defaults are used.
+    pub path: Option<PathBuf>,
+    /// Unknown keys; these are ignored when loading but fail `config check`.
+    pub warnings: Vec<ConfigIssue>,
 }
 
-// Load configuration from file
-pub fn load_config() -> String {
-    // Try config locations in order of priority
-    let config_paths = vec![
-        // 1. ~/.config/izone/config.toml (XDG standard)
+/// The config search paths, highest priority first:
+/// 1. ~/.config/izone/config.toml (XDG standard)
+/// 2. ./izone.toml (current directory)
+/// 3. ~/.izone.toml (home directory)
+pub fn config_search_paths() -> Vec<PathBuf> {
+    vec![
         dirs::config_dir().map(|mut p| {
             p.push("izone");
             p.push("config.toml");
             p
         }),
-        // 2. ./izone.toml (current directory)
         Some(PathBuf::from("izone.toml")),
-        // 3. ~/.izone.toml (home directory)
         dirs::home_dir().map(|mut p| {
             p.push(".izone.toml");
             p
         }),
-    ];
-
-    // Try each config path
-    for path_option in config_paths {
-        if let Some(path) = path_option {
-            if path.exists() {
-                if let Ok(contents) = fs::read_to_string(&path) {
-                    if let Ok(config) = toml::from_str::<Config>(&contents) {
-                        if let Some(ip) = config.izone_ip {
-                            return ip;
-                        }
-                    }
-                }
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// 1-based line of a byte offset.
+fn line_of(contents: &str, offset: usize) -> usize {
+    contents[..offset.min(contents.len())].matches('\n').count() + 1
+}
+
+/// Best-effort line of a dotted key path such as `profiles.office.ip`.
+fn key_line(contents: &str, path: &str) -> Option<usize> {
+    let (section, key) = path.rsplit_once('.').unwrap_or(("", path));
+    let mut current = String::new();
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if let Some(header) = line.strip_prefix('[') {
+            current = header.trim_matches(['[', ']']).trim().to_string();
+            // Unknown tables are reported by their header
+            if current == path || current.starts_with(&format!("{}.", path)) {
+                return Some(i + 1);
             }
+        } else if current == section
+            && let Some((name, _)) = line.split_once('=')
+            && name.trim().trim_matches('"') == key
+        {
+            return Some(i + 1);
         }
     }
-
-    // Fallback to default
-    DEFAULT_IZONE_IP.to_string()
+    None
 }
 
-// Lazy static to hold the loaded IP address
-lazy_static::lazy_static! {
-    pub static ref IZONE_IP: String = load_config();
+/// Parses config text. Syntax and type errors fail; unknown keys are
+/// returned as issues.
+pub fn parse_config(contents: &str) -> std::result::Result<(Config, Vec<ConfigIssue>), ConfigIssue> {
+    let mut unknown = Vec::new();
+    let deserializer = toml::Deserializer::new(contents);
+    let config: Config = serde_ignored::deserialize(deserializer, |path| unknown.push(path.to_string()))
+        .map_err(|e: toml::de::Error| ConfigIssue {
+            line: e.span().map(|span| line_of(contents, span.start)),
+            message: e.message().to_string(),
+        })?;
+
+    let issues = unknown
+        .into_iter()
+        .map(|path| ConfigIssue { line: key_line(contents, &path), message: format!("unknown key '{}'", path) })
+        .collect();
+    Ok((config, issues))
 }
 
-// Define zones and their corresponding API indices.
-lazy_static::lazy_static! {
-    pub static ref ZONES: HashMap<&'static str, u8> = {
-        let mut m = HashMap::new();
-        m.insert("kitchen", 0);
-        m.insert("theatre", 1);
-        m.insert("living", 2);
-        m.insert("master", 3);
-        m.insert("work", 4);
-        m.insert("guest", 5);
-        m.insert("rayden", 6);
-        m.insert("rumpus", 7);
-        m
+/// Checks values that parse but cannot work, e.g. an IP without http://.
+pub fn check_config(config: &Config, contents: &str) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+    let mut check = |path: String, problem: Option<String>| {
+        if let Some(message) = problem {
+            issues.push(ConfigIssue { line: key_line(contents, &path), message: format!("{}: {}", path, message) });
+        }
     };
+
+    let ip_problem = |ip: &Option<String>| {
+        ip.as_deref()
+            .filter(|ip| !ip.eq_ignore_ascii_case("auto") && !ip.starts_with("http://") && !ip.starts_with("https://"))
+            .map(|ip| format!("'{}' must start with http:// or https://, or be \"auto\"", ip))
+    };
+    let output_problem = |output: &Option<String>| {
+        output
+            .as_deref()
+            .filter(|o| !OUTPUT_FORMATS.contains(&o.to_lowercase().as_str()))
+            .map(|o| format!("'{}' is not one of {}", o, OUTPUT_FORMATS.join(", ")))
+    };
+    let zone_problems = |zones: &Option<BTreeMap<String, u8>>| -> Vec<(String, String)> {
+        zones
+            .iter()
+            .flatten()
+            .filter(|(_, index)| **index >= MAX_ZONES)
+            .map(|(name, index)| (name.clone(), format!("index {} is out of range (0-{})", index, MAX_ZONES - 1)))
+            .collect()
+    };
+
+    check("izone_ip".to_string(), ip_problem(&config.izone_ip));
+    check("output".to_string(), output_problem(&config.output));
+    for (name, problem) in zone_problems(&config.zones) {
+        check(format!("zones.{}", name), Some(problem));
+    }
+    if let Some(default) = &config.default_profile
+        && !config.profiles.contains_key(default)
+    {
+        check("default_profile".to_string(), Some(format!("no [profiles.{}] table", default)));
+    }
+    for (profile_name, profile) in &config.profiles {
+        check(format!("profiles.{}.izone_ip", profile_name), ip_problem(&profile.izone_ip));
+        check(format!("profiles.{}.output", profile_name), output_problem(&profile.output));
+        for (name, problem) in zone_problems(&profile.zones) {
+            check(format!("profiles.{}.zones.{}", profile_name, name), Some(problem));
+        }
+    }
+    issues
 }
 
-// Global verbose flag (needs to be pub for external access)
-pub static mut VERBOSE: bool = false;
\ No newline at end of file
+/// Loads `explicit` (from `--config` or `IZONE_CONFIG`) or the first config
+/// file that exists in the search paths. A file that exists but does not
+/// parse is an error rather than being skipped.
+pub fn load_config(explicit: Option<&Path>) -> crate::Result<LoadedConfig> {
+    let path = match explicit {
+        Some(path) if !path.exists() => {
+            return Err(crate::IzoneError::validation(format!("Config file '{}' not found.", path.display())));
+        }
+        Some(path) => path.to_path_buf(),
+        None => match config_search_paths().into_iter().find(|p| p.exists()) {
+            Some(path) => path,
+            None => return Ok(LoadedConfig::default()),
+        },
+    };
+
+    let contents = fs::read_to_string(&path).map_err(|e| {
+        crate::IzoneError::validation(format!("Cannot read config file '{}': {}", path.display(), e))
+    })?;
+    let (config, warnings) = parse_config(&contents).map_err(|issue| {
+        crate::IzoneError::validation(format!(
+            "Invalid config file '{}', {}\nRun 'izone config check' for details.",
+            path.display(),
+            issue
+        ))
+    })?;
+    for warning in &warnings {
+        log::warn!("{}: {} (ignored)", path.display(), warning);
+    }
+
+    Ok(LoadedConfig { config, path: Some(path), warnings })
+}
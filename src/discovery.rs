@@ -0,0 +1,240 @@
+// izone/src/discovery.rs
+
+//! Bridge discovery: "IASD" is broadcast to UDP port 12107 and every iZone
+//! bridge on the LAN replies with
+//! `ASPort_12107,Mac_<id>,IP_<ip>,iZoneV2,iLight,iDrate,iPower,Split`
+//! (unsupported systems are "X" or missing).
+
+use std::fs;
+use std::net::{SocketAddr, UdpSocket};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::error::{IzoneError, Result};
+
+/// UDP port bridges listen on for discovery.
+pub const DISCOVERY_PORT: u16 = 12107;
+/// Where the discovery request is sent by default.
+pub const BROADCAST_TARGET: &str = "255.255.255.255:12107";
+/// The discovery request payload.
+pub const DISCOVERY_MESSAGE: &[u8] = b"IASD";
+/// How long to collect replies by default.
+pub const DEFAULT_DISCOVERY_WAIT_MS: u64 = 2000;
+
+/// A bridge that answered the discovery broadcast.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Bridge {
+    /// 9-digit unique system ID (the `Mac_` field).
+    pub id: String,
+    pub ip: String,
+    pub port: u16,
+    /// Supported systems: iZone, iZoneV2, iLight, iDrate, iPower, Split.
+    pub capabilities: Vec<String>,
+}
+
+impl Bridge {
+    /// Whether the bridge supports the V2 API this crate talks.
+    pub fn supports_v2(&self) -> bool {
+        self.capabilities.iter().any(|c| c == "iZoneV2")
+    }
+
+    /// Base URL for an [`IzoneClient`](crate::IzoneClient).
+    pub fn url(&self) -> String {
+        format!("http://{}", self.ip)
+    }
+}
+
+/// Parses a discovery reply, `None` if it is not one.
+pub fn parse_reply(reply: &str) -> Option<Bridge> {
+    let mut fields = reply.trim().trim_end_matches('\0').split(',').map(str::trim);
+    let port = fields.next()?.strip_prefix("ASPort_")?.parse().ok()?;
+    let id = fields.next()?.strip_prefix("Mac_")?.to_string();
+    let ip = fields.next()?.strip_prefix("IP_")?.to_string();
+    let capabilities = fields.filter(|f| !f.is_empty() && *f != "X").map(str::to_string).collect();
+    Some(Bridge { id, ip, port, capabilities })
+}
+
+/// Sends "IASD" to `target` and collects replies for `wait`. Pass
+/// [`BROADCAST_TARGET`] to search the LAN, or a unicast address (e.g. a
+/// local test responder) to ask a single host.
+pub fn discover(target: &str, wait: Duration) -> Result<Vec<Bridge>> {
+    let udp_error = |e: std::io::Error| IzoneError::Connection {
+        url: format!("udp://{}", target),
+        details: e.to_string(),
+    };
+
+    let target: SocketAddr = target
+        .parse()
+        .map_err(|_| IzoneError::validation(format!("Invalid discovery address '{}'. Expected IP:PORT.", target)))?;
+    let socket = UdpSocket::bind(("0.0.0.0", 0)).map_err(udp_error)?;
+    socket.set_broadcast(true).map_err(udp_error)?;
+    log::debug!("UDP {} {:?}", target, String::from_utf8_lossy(DISCOVERY_MESSAGE));
+    socket.send_to(DISCOVERY_MESSAGE, target).map_err(udp_error)?;
+
+    let deadline = Instant::now() + wait;
+    let mut bridges: Vec<Bridge> = Vec::new();
+    let mut buf = [0u8; 512];
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        socket.set_read_timeout(Some(remaining)).map_err(udp_error)?;
+        match socket.recv_from(&mut buf) {
+            Ok((len, from)) => {
+                let reply = String::from_utf8_lossy(&buf[..len]);
+                log::debug!("UDP reply from {}: {}", from, reply.trim());
+                match parse_reply(&reply) {
+                    Some(bridge) if !bridges.iter().any(|b| b.id == bridge.id) => bridges.push(bridge),
+                    Some(_) => {}
+                    None => log::debug!("Ignoring non-discovery reply from {}", from),
+                }
+            }
+            Err(e) if matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) => break,
+            Err(e) => return Err(udp_error(e)),
+        }
+    }
+    Ok(bridges)
+}
+
+/// File holding the URL of the last bridge found for `izone_ip = "auto"`.
+/// Each profile has its own file (`bridge-<profile>`), so profiles on
+/// different bridges do not overwrite each other; `None` is the top level.
+pub fn cache_path(profile: Option<&str>) -> Option<PathBuf> {
+    let name = match profile {
+        Some(profile) => {
+            let safe: String = profile
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+                .collect();
+            format!("bridge-{}", safe)
+        }
+        None => "bridge".to_string(),
+    };
+    dirs::cache_dir().map(|mut p| {
+        p.push("izone");
+        p.push(name);
+        p
+    })
+}
+
+/// Remembers `url` for the next `izone_ip = "auto"` lookup of `profile`.
+pub fn save_cached_url(profile: Option<&str>, url: &str) {
+    let Some(path) = cache_path(profile) else { return };
+    let result = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(&path, url));
+    if let Err(e) = result {
+        log::warn!("Could not cache bridge address in {}: {}", path.display(), e);
+    }
+}
+
+/// The cached bridge URL of `profile`, if any.
+pub fn cached_url(profile: Option<&str>) -> Option<String> {
+    let url = fs::read_to_string(cache_path(profile)?).ok()?;
+    let url = url.trim();
+    (!url.is_empty()).then(|| url.to_string())
+}
+
+/// Resolves `izone_ip = "auto"`: the cached bridge if there is one, otherwise
+/// the first V2 bridge that answers the broadcast (which is then cached).
+pub fn auto_bridge_url(profile: Option<&str>, wait: Duration) -> Result<String> {
+    if let Some(url) = cached_url(profile) {
+        log::debug!("Using cached bridge {}", url);
+        return Ok(url);
+    }
+    rediscover_bridge_url(profile, wait)
+}
+
+/// Broadcasts for the bridge, ignoring the cache, and caches the first V2
+/// bridge that answers. Used when the cached bridge stops answering, e.g.
+/// after its DHCP lease changed.
+pub fn rediscover_bridge_url(profile: Option<&str>, wait: Duration) -> Result<String> {
+    let bridges = discover(BROADCAST_TARGET, wait)?;
+    if bridges.is_empty() {
+        return Err(IzoneError::Connection {
+            url: format!("udp://{}", BROADCAST_TARGET),
+            details: "no iZone bridge answered the discovery broadcast (izone_ip = \"auto\")".to_string(),
+        });
+    }
+    let bridge = bridges.iter().find(|b| b.supports_v2()).ok_or_else(|| {
+        IzoneError::validation(format!(
+            "Found {} bridge(s), but none supports the iZone V2 API.\nRun 'izone discover' for details.",
+            bridges.len()
+        ))
+    })?;
+    if bridges.len() > 1 {
+        log::warn!("Several bridges found, using {} ({}). Set izone_ip to pick another.", bridge.id, bridge.ip);
+    }
+
+    let url = bridge.url();
+    save_cached_url(profile, &url);
+    Ok(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn parse_reply_reads_a_bridge_reply() {
+        let bridge = parse_reply("ASPort_12107,Mac_000013170,IP_192.168.1.130,iZoneV2,iLight,X,X,Split\0").unwrap();
+        assert_eq!(bridge.id, "000013170");
+        assert_eq!(bridge.ip, "192.168.1.130");
+        assert_eq!(bridge.port, 12107);
+        assert_eq!(bridge.capabilities, ["iZoneV2", "iLight", "Split"]);
+        assert!(bridge.supports_v2());
+        assert_eq!(bridge.url(), "http://192.168.1.130");
+    }
+
+    #[test]
+    fn parse_reply_rejects_truncated_replies() {
+        assert_eq!(parse_reply(""), None);
+        assert_eq!(parse_reply("ASPort_12107"), None);
+        assert_eq!(parse_reply("ASPort_12107,Mac_000013170"), None);
+        assert_eq!(parse_reply("ASPort_,Mac_000013170,IP_192.168.1.130"), None);
+    }
+
+    #[test]
+    fn parse_reply_rejects_foreign_replies() {
+        assert_eq!(parse_reply("IASD"), None);
+        assert_eq!(parse_reply("HTTP/1.1 200 OK"), None);
+        assert_eq!(parse_reply("Mac_000013170,ASPort_12107,IP_192.168.1.130"), None);
+    }
+
+    #[test]
+    fn discover_collects_replies_from_a_local_responder() {
+        let responder = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let target = responder.local_addr().unwrap().to_string();
+        let handle = thread::spawn(move || {
+            let mut buf = [0u8; 16];
+            let (len, from) = responder.recv_from(&mut buf).unwrap();
+            assert_eq!(&buf[..len], DISCOVERY_MESSAGE);
+            for reply in [
+                "ASPort_12107,Mac_000013170,IP_10.0.0.5,iZoneV2,X,X,X,X",
+                "not a discovery reply",
+                "ASPort_12107,Mac_000013170,IP_10.0.0.5,iZoneV2,X,X,X,X",
+                "ASPort_12107,Mac_000020001,IP_10.0.0.6,iZone,X,X,X,X",
+            ] {
+                responder.send_to(reply.as_bytes(), from).unwrap();
+            }
+        });
+
+        let bridges = discover(&target, Duration::from_millis(500)).unwrap();
+        handle.join().unwrap();
+
+        let ids: Vec<&str> = bridges.iter().map(|b| b.id.as_str()).collect();
+        assert_eq!(ids, ["000013170", "000020001"]);
+        assert!(bridges[0].supports_v2());
+        assert!(!bridges[1].supports_v2());
+    }
+
+    #[test]
+    fn discover_rejects_an_invalid_target() {
+        assert!(matches!(discover("not-an-address", Duration::from_millis(10)), Err(IzoneError::Validation(_))));
+    }
+}
@@ -0,0 +1,105 @@
+// izone/src/error.rs
+
+use std::fmt;
+
+/// Errors returned by the iZone client and command layer.
+#[derive(Debug)]
+pub enum IzoneError {
+    /// The controller could not be reached.
+    Connection { url: String, details: String },
+    /// The controller did not answer within the configured timeout.
+    Timeout { url: String, details: String },
+    /// The controller answered with a non-success HTTP status; `body` is the
+    /// response text with secrets masked (see [`crate::api::mask_secrets`]).
+    HttpStatus { url: String, status: u16, body: String },
+    /// The controller reported an error in its response text, kept in
+    /// `message` with secrets masked.
+    Controller { url: String, message: String },
+    /// The response could not be parsed; `body` holds the response text with
+    /// secrets masked.
+    Parse { url: String, what: String, details: String, body: String },
+    /// The system is locked (`LockStatus`), so the command was not sent.
+    Locked { url: String },
+    /// A value was rejected before anything was sent to the controller.
+    Validation(String),
+}
+
+/// Result type used throughout the crate.
+pub type Result<T> = std::result::Result<T, IzoneError>;
+
+impl IzoneError {
+    /// Shorthand for building a [`IzoneError::Validation`].
+    pub fn validation(message: impl Into<String>) -> Self {
+        IzoneError::Validation(message.into())
+    }
+
+    /// Whether the request may succeed if sent again (connection failure,
+    /// timeout or a 5xx status from the controller).
+    pub fn is_transient(&self) -> bool {
+        match self {
+            IzoneError::Connection { .. } | IzoneError::Timeout { .. } => true,
+            IzoneError::HttpStatus { status, .. } => *status >= 500,
+            _ => false,
+        }
+    }
+
+    /// Maps a reqwest transport error to `Timeout` or `Connection`.
+    pub(crate) fn from_reqwest(url: &str, e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            IzoneError::Timeout { url: url.to_string(), details: e.to_string() }
+        } else {
+            IzoneError::Connection { url: url.to_string(), details: e.to_string() }
+        }
+    }
+}
+
+impl fmt::Display for IzoneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IzoneError::Connection { url, details } => write!(
+                f,
+                "Failed to connect to iZone controller at {}.\n\
+                Ensure the IP address is correct and the controller is reachable in your network.\n\
+                Error details: {}",
+                url, details
+            ),
+            IzoneError::Timeout { url, details } => write!(
+                f,
+                "Timed out waiting for iZone controller at {}.\n\
+                Ensure the IP address is correct and the controller is reachable in your network.\n\
+                Error details: {}",
+                url, details
+            ),
+            IzoneError::HttpStatus { url, status, body } => write!(
+                f,
+                "Unexpected response from iZone controller at {}.\n\
+                Ensure your configuration has the correct iZone IP and the controller is reachable in your network.\n\
+                Error details: HTTP status {} - {}",
+                url, status, body
+            ),
+            IzoneError::Controller { url, message } => write!(
+                f,
+                "iZone controller at {} rejected the command.\n\
+                Check the value is supported by your system.\n\
+                Error details: {}",
+                url, message
+            ),
+            IzoneError::Parse { url, what, details, .. } => write!(
+                f,
+                "Unexpected response from iZone controller at {}.\n\
+                Ensure your configuration has the correct iZone IP and the controller is reachable in your network.\n\
+                Error details: Failed to parse {} response - {}",
+                url, what, details
+            ),
+            IzoneError::Locked { url } => write!(
+                f,
+                "The iZone system at {} is locked, enter the keycode to unlock it.\n\
+                Use the iZone controller or 'izone unlock --code <keycode>'; commands are ignored until then.",
+                url
+            ),
+            IzoneError::Validation(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for IzoneError {}
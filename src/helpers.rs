@@ -21,6 +21,30 @@ pub fn get_colored_system_mode(sys_mode: u8) -> String {
     }
 }
 
+/// Converts raw system mode integer to plain text (e.g. for JSON output).
+pub fn get_system_mode_text(sys_mode: u8) -> String {
+    match sys_mode {
+        1 => "Cool".to_string(),
+        2 => "Heat".to_string(),
+        3 => "Vent".to_string(),
+        4 => "Dry".to_string(),
+        5 => "Auto".to_string(),
+        _ => format!("Mode({})", sys_mode),
+    }
+}
+
+/// Converts ZoneMode_e to plain text.
+pub fn get_zone_mode_text(mode: u8) -> String {
+    match mode {
+        1 => "Open".to_string(),
+        2 => "Close".to_string(),
+        3 => "Auto".to_string(),
+        4 => "Override".to_string(),
+        5 => "Constant".to_string(),
+        _ => format!("Mode({})", mode),
+    }
+}
+
 /// Converts human-readable system mode string to its corresponding u8 value.
 /// Limited to Auto, Cool, Heat, Vent, Dry.
 pub fn get_system_mode_value(mode_name: &str) -> Option<u8> {
@@ -48,17 +72,57 @@ pub fn get_fan_speed_text(sys_fan: u8) -> String {
 }
 
 /// Converts human-readable fan speed string to its corresponding u8 value.
-/// Limited to Low, Medium, High, Auto.
+/// Limited to Low, Medium, High, Auto, Top.
 pub fn get_fan_speed_value(fan_speed_name: &str) -> Option<u8> {
     match fan_speed_name.to_lowercase().as_str() {
         "low" => Some(1),
         "medium" => Some(2),
         "high" => Some(3),
         "auto" => Some(4),
+        "top" => Some(5),
+        _ => None,
+    }
+}
+
+/// Converts FanAutoType_e to text.
+pub fn get_fan_auto_type_text(fan_type: u8) -> String {
+    match fan_type {
+        0 => "2 Speed".to_string(),
+        1 => "3 Speed".to_string(),
+        2 => "Variable Speed".to_string(),
+        3 => "4 Speed".to_string(),
+        _ => format!("FanAutoType({})", fan_type),
+    }
+}
+
+/// Converts a fan type name ("2-speed", "3-speed", "variable", "4-speed") to FanAutoType_e.
+pub fn get_fan_auto_type_value(name: &str) -> Option<u8> {
+    match name.to_lowercase().as_str() {
+        "2-speed" | "2speed" => Some(0),
+        "3-speed" | "3speed" => Some(1),
+        "variable" | "variable-speed" => Some(2),
+        "4-speed" | "4speed" => Some(3),
         _ => None,
     }
 }
 
+/// SysFan_e speeds the AC unit control screen offers for the `FanAutoEn` /
+/// `FanAutoType` settings. Without fan auto only Low, Medium and High are
+/// allowed; a 2-speed fan has no Medium and only a 4-speed fan has Top.
+/// Firmware that does not send `FanAutoEn` keeps Low, Medium, High and Auto.
+pub fn get_allowed_fan_speeds(fan_auto_en: Option<u8>, fan_auto_type: u8) -> &'static [u8] {
+    match fan_auto_en {
+        None => return &[1, 2, 3, 4],
+        Some(0) => return &[1, 2, 3],
+        Some(_) => {}
+    }
+    match fan_auto_type {
+        0 => &[1, 3, 4],
+        3 => &[1, 2, 3, 5, 4],
+        _ => &[1, 2, 3, 4],
+    }
+}
+
 /// Converts ZoneType_e to human-readable text.
 pub fn get_zone_type_text(type_code: u8) -> String {
     match type_code {
@@ -69,6 +133,42 @@ pub fn get_zone_type_text(type_code: u8) -> String {
     }
 }
 
+/// Converts a zone type name ("open-close", "constant", "auto") to ZoneType_e.
+pub fn get_zone_type_value(type_name: &str) -> Option<u8> {
+    match type_name.to_lowercase().as_str() {
+        "open-close" | "openclose" => Some(1),
+        "constant" => Some(2),
+        "auto" => Some(3),
+        _ => None,
+    }
+}
+
+/// Converts RoomSensorType_t to text.
+pub fn get_sensor_type_text(sensor: u8) -> String {
+    match sensor {
+        0 => "CCTS".to_string(),
+        1 => "CSM".to_string(),
+        2 => "CZCO (iSense)".to_string(),
+        3 => "CRFS (wireless)".to_string(),
+        4 => "CS (wired)".to_string(),
+        255 => "None".to_string(),
+        _ => format!("Sensor({})", sensor),
+    }
+}
+
+/// Converts a sensor name ("ccts", "csm", "czco", "crfs", "cs", "none") to RoomSensorType_t.
+pub fn get_sensor_type_value(sensor_name: &str) -> Option<u8> {
+    match sensor_name.to_lowercase().as_str() {
+        "ccts" => Some(0),
+        "csm" => Some(1),
+        "czco" => Some(2),
+        "crfs" => Some(3),
+        "cs" => Some(4),
+        "none" => Some(255),
+        _ => None,
+    }
+}
+
 /// Returns the raw BatteryLevel_e value, optionally colored red if 0.
 pub fn get_battery_level_text(batt_code: u8) -> String {
     if batt_code == 0 {
@@ -87,6 +187,217 @@ pub fn get_sensor_fault_text(fault_code: u8) -> String {
     }
 }
 
+/// Converts RfSignalLevel_e to colored text.
+pub fn get_rf_signal_text(level: u8) -> String {
+    match level {
+        0 => "Full".green().to_string(),
+        1 => "Half".green().to_string(),
+        2 => "Quarter".yellow().to_string(),
+        3 => "None".red().to_string(),
+        _ => format!("Unknown ({})", level).normal().to_string(),
+    }
+}
+
+/// Converts a CRFS2 transmit interval in seconds to Crfs2TxInterval_e
+/// (2s = 1, 4s = 2 ... 256s = 8).
+pub fn get_tx_interval_value(seconds: u16) -> Option<u8> {
+    (1..=8u8).find(|&v| 1u16 << v == seconds)
+}
+
+/// Converts AcCpuType_e to the name of the controller board.
+pub fn get_cpu_type_text(cpu_type: u8) -> String {
+    match cpu_type {
+        0 => "C220".to_string(),
+        1 => "C150".to_string(),
+        2 => "Coolbreeze".to_string(),
+        _ => format!("CpuType({})", cpu_type),
+    }
+}
+
+/// Converts OemMake_e to the system make (with the product name in brackets).
+pub fn get_oem_make_text(oem_make: u8) -> String {
+    match oem_make {
+        0 => "Airstream (iZone)".to_string(),
+        1 => "Metalflex (MyZone)".to_string(),
+        2 => "Westaflex".to_string(),
+        3 => "Electrolux (Kelvinator)".to_string(),
+        4 => "Consolidated".to_string(),
+        5 => "Toshiba (T-Zone)".to_string(),
+        6 => "Air Additions (RayZone)".to_string(),
+        7 => "Coolbreeze Evap".to_string(),
+        _ => format!("OemMake({})", oem_make),
+    }
+}
+
+/// Converts UnitBrand_e (the detected AC unit) to text.
+pub fn get_unit_brand_text(brand: u8) -> String {
+    const BRANDS: [&str; 24] = [
+        "Panasonic/Toshiba", "Daikin", "Mitsubishi Electric", "LG (301)", "LG (310)", "Fujitsu",
+        "Samsung", "Temperzone", "Mitsubishi Heavy Industries", "Gas Heat Add-On Cool", "Generic",
+        "Unknown", "Hitachi", "AA Gen III", "Fujitsu (Intesis)", "LG (485)", "York", "Haier",
+        "LG Coolink", "Braemar", "Samsung (2)", "Rinnai Networker", "Coolbreeze Evap", "Midea Split System",
+    ];
+    match brand {
+        1..=24 => BRANDS[brand as usize - 1].to_string(),
+        _ => format!("UnitBrand({})", brand),
+    }
+}
+
+/// Converts UnitBrandSet_t (the brand chosen during installation) to text.
+pub fn get_unit_brand_set_text(brand: u8) -> String {
+    const BRANDS: [&str; 23] = [
+        "Auto Detect", "Panasonic", "Daikin", "Fujitsu", "Fujitsu 20kW", "Mitsubishi Electric",
+        "Mitsubishi Heavy Industries", "Hitachi", "Toshiba", "LG Coolink", "Samsung", "Temperzone",
+        "Universal Control Module", "Actron", "Braemar", "Haier", "Kaden", "Kelvinator", "Midea",
+        "Rinnai", "York", "iZone", "Samsung (2)",
+    ];
+    BRANDS.get(brand as usize).map_or_else(|| format!("UnitBrandSet({})", brand), |b| b.to_string())
+}
+
+/// Converts ReturnAirSensor_e (the unit's control source) to text.
+pub fn get_ras_text(ras: u8) -> String {
+    match ras {
+        1 => "RAS".to_string(),
+        2 => "Master".to_string(),
+        3 => "Zones".to_string(),
+        _ => format!("RAS({})", ras),
+    }
+}
+
+/// Converts a control source name ("ras", "master", "zones") to ReturnAirSensor_e.
+pub fn get_ras_value(name: &str) -> Option<u8> {
+    match name.to_lowercase().as_str() {
+        "ras" | "return-air" => Some(1),
+        "master" => Some(2),
+        "zones" => Some(3),
+        _ => None,
+    }
+}
+
+/// Converts a linkage direction and function ("izone"/"unit", "on-off"/"mode"/
+/// "fan"/"setpoint") to its `SystemV2` flag and command key, e.g. "iZoneMode".
+/// "izone" means iZone controls the AC unit, "unit" the unit's own controller
+/// controls iZone (`Ext*`).
+pub fn get_linkage_key(direction: &str, function: &str) -> Option<&'static str> {
+    let keys = match direction.to_lowercase().as_str() {
+        "izone" => ["iZoneOnOff", "iZoneMode", "iZoneFan", "iZoneSetpoint"],
+        "unit" | "ext" => ["ExtOnOff", "ExtMode", "ExtFan", "ExtSetpoint"],
+        _ => return None,
+    };
+    match function.to_lowercase().as_str() {
+        "on-off" | "onoff" | "power" => Some(keys[0]),
+        "mode" => Some(keys[1]),
+        "fan" => Some(keys[2]),
+        "setpoint" => Some(keys[3]),
+        _ => None,
+    }
+}
+
+/// Maps a panel setting name to its command key, e.g. "wired-leds" ->
+/// "SetWiredLeds". `OutOfViewRAS` has no documented command, so it is not
+/// settable.
+pub fn get_panel_key(setting: &str) -> Option<&'static str> {
+    match setting.to_lowercase().as_str() {
+        "auto-off" => Some("AutoOff"),
+        "room-temp" | "room-temp-display" => Some("RoomTempDisp"),
+        "wired-leds" => Some("SetWiredLeds"),
+        "hide-induct" => Some("HideInduct"),
+        "show-actual-temps" | "actual-temps" => Some("ShowActTemps"),
+        _ => None,
+    }
+}
+
+/// `CtrlZone` value meaning the unit runs from its own setpoint, not a zone.
+pub const CTRL_ZONE_UNIT_SETPOINT: u8 = 15;
+
+/// Converts TemperzoneFanType_t to text.
+pub fn get_temperzone_fan_type_text(fan_type: u8) -> String {
+    match fan_type {
+        0 => "Variable Speed".to_string(),
+        1 => "3 Speed".to_string(),
+        _ => format!("FanType({})", fan_type),
+    }
+}
+
+/// Converts a fan type name ("variable", "3-speed") to TemperzoneFanType_t.
+pub fn get_temperzone_fan_type_value(name: &str) -> Option<u8> {
+    match name.to_lowercase().as_str() {
+        "variable" | "variable-speed" => Some(0),
+        "3-speed" | "3speed" => Some(1),
+        _ => None,
+    }
+}
+
+/// Converts TemperzoneModeType_t to text.
+pub fn get_temperzone_mode_type_text(mode_type: u8) -> String {
+    match mode_type {
+        0 => "No Expansion".to_string(),
+        1 => "Single Expansion".to_string(),
+        2 => "Series Expansion".to_string(),
+        3 => "Dry Mode".to_string(),
+        _ => format!("ModeType({})", mode_type),
+    }
+}
+
+/// Converts a mode type name ("no-expansion", "single-expansion",
+/// "series-expansion", "dry-mode") to TemperzoneModeType_t.
+pub fn get_temperzone_mode_type_value(name: &str) -> Option<u8> {
+    match name.to_lowercase().as_str() {
+        "no-expansion" => Some(0),
+        "single-expansion" => Some(1),
+        "series-expansion" => Some(2),
+        "dry-mode" => Some(3),
+        _ => None,
+    }
+}
+
+/// GasHeatType_e names as accepted on the command line, indexed by value.
+pub const GAS_HEAT_TYPE_NAMES: [&str; 14] = [
+    "heat-only", "cool-only", "1-heat-1-cool", "2-heat-1-cool", "1-heat-pump", "1-heat-pump-3-speed",
+    "1-heat-pump-1-heat", "2-heat-pump-1-heat", "1-gas-heat", "2-gas-heat-2-cool", "remote-on-off",
+    "ventilation", "aa-gen-iii", "brivis-1-heat-1-cool",
+];
+
+/// Converts GasHeatType_e to text.
+pub fn get_gas_heat_type_text(gas_type: u8) -> String {
+    match gas_type {
+        0 => "Heat Only, 1 Speed Fan".to_string(),
+        1 => "Cool Only, 1 Speed Fan".to_string(),
+        2 => "1 Heat and 1 Cool, 1 Speed Fan".to_string(),
+        3 => "2 Heat and 1 Cool, 1 Speed Fan".to_string(),
+        4 => "1 Heat Pump, 1 Speed Fan".to_string(),
+        5 => "1 Heat Pump, 3 Speed Fan".to_string(),
+        6 => "1 Heat Pump and 1 Heat, 1 Speed Fan".to_string(),
+        7 => "2 Heat Pump and 1 Heat, 1 Speed Fan".to_string(),
+        8 => "1 Gas Heat".to_string(),
+        9 => "2 Gas Heat and 2 Cool, 1 Speed Fan".to_string(),
+        10 => "Remote On/Off".to_string(),
+        11 => "Ventilation Control".to_string(),
+        12 => "AA Gen III".to_string(),
+        13 => "Brivis 1 Heat and 1 Cool, 1 Speed Fan".to_string(),
+        _ => format!("GasHeatType({})", gas_type),
+    }
+}
+
+/// Converts a gas heat type name (see `GAS_HEAT_TYPE_NAMES`) or its number
+/// (0-13) to GasHeatType_e.
+pub fn get_gas_heat_type_value(name: &str) -> Option<u8> {
+    let name = name.to_lowercase();
+    match name.parse::<u8>() {
+        Ok(value) => (usize::from(value) < GAS_HEAT_TYPE_NAMES.len()).then_some(value),
+        Err(_) => GAS_HEAT_TYPE_NAMES.iter().position(|n| *n == name).map(|i| i as u8),
+    }
+}
+
+/// Converts SysType to text: 310 supports unit control, 210 does not.
+pub fn get_sys_type_text(sys_type: u16) -> String {
+    match sys_type {
+        310 => "310 (with unit control)".to_string(),
+        210 => "210 (no unit control)".to_string(),
+        _ => sys_type.to_string(),
+    }
+}
+
 /// Custom deserializer for booleans that are represented as 0 or 1 integers.
 pub fn deserialize_int_as_bool<'de, D>(deserializer: D) -> Result<bool, D::Error>
 where
@@ -0,0 +1,41 @@
+// izone/src/lib.rs
+
+//! Library for controlling Airstream iZone air conditioning systems through
+//! the local iZone V2 HTTP API (`/iZoneRequestV2` and `/iZoneCommandV2`).
+//!
+//! ```no_run
+//! use izone::{IzoneClient, ZoneMode};
+//!
+//! let client = IzoneClient::new("http://192.168.1.130").unwrap();
+//! let system = client.system().unwrap();
+//! println!("Return air: {}", izone::helpers::format_temp(system.temp));
+//! client.set_zone_mode(2, ZoneMode::Auto).unwrap();
+//! ```
+//!
+//! On a tokio runtime use [`AsyncIzoneClient`], which has the same methods:
+//!
+//! ```no_run
+//! # async fn example() -> izone::Result<()> {
+//! let client = izone::AsyncIzoneClient::new("http://192.168.1.130")?;
+//! let zone = client.zone(0).await?;
+//! println!("{} is at {}", zone.name, izone::helpers::format_temp(zone.temp));
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod api;
+pub mod async_client;
+pub mod client;
+pub mod constants;
+pub mod discovery;
+pub mod error;
+pub mod helpers;
+pub mod models;
+pub mod report;
+pub mod zone_map;
+
+pub use async_client::AsyncIzoneClient;
+pub use client::{ClientOptions, IzoneClient};
+pub use error::{IzoneError, Result};
+pub use models::ZoneMode;
+pub use zone_map::ZoneMap;
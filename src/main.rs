@@ -1,37 +1,91 @@
 // izone/src/main.rs
 
 use clap::{Args, Parser};
-use reqwest::blocking::Client;
+use log::LevelFilter;
+use std::fs::OpenOptions;
+use std::path::PathBuf;
 use std::process::exit;
 use colored::Colorize; // New: Import Colorize trait for coloring strings
 
-extern crate lazy_static;
+use izone::constants;
+use izone::{ClientOptions, IzoneClient, IzoneError, ZoneMap};
 
 // Declare modules
-mod api;
 mod commands;
-mod constants;
-mod helpers;
-mod models;
 
 // Use specific functions from modules
 use crate::commands::system;
 use crate::commands::zones;
 use crate::commands::schedules; // New: Import schedules module
+use crate::commands::output::OutputFormat;
+use crate::commands::discover;
+use crate::commands::profile;
+use crate::commands::config_file;
+use crate::commands::faults;
+use crate::commands::temperzone;
+use crate::commands::devices;
+use crate::commands::gasheat;
 
 /// Command-line arguments using Clap
-#[derive(Parser, Debug)]
+#[derive(Parser, Debug, Clone)]
 #[command(name = "izone", author = "Rufus P. Shrestha", version = env!("CARGO_PKG_VERSION"))]
 #[command(about = "Airstream iZone Controller", long_about = None)]
 struct Cli {
-    #[arg(short = 'v', long = "verbose", help = "Show full JSON output for status queries and API responses.")]
-    verbose: bool,
+    #[arg(short = 'v', long = "verbose", action = clap::ArgAction::Count, global = true, help = "Log API requests, responses and timing to stderr (-v debug, -vv trace).")]
+    verbose: u8,
+
+    #[arg(long = "log-level", value_enum, global = true, help = "Log level (overrides -v and IZONE_LOG).")]
+    log_level: Option<LogLevel>,
+
+    #[arg(long = "log-file", global = true, help = "Append log output to this file instead of stderr.")]
+    log_file: Option<PathBuf>,
+
+    #[arg(long = "timeout-ms", global = true, help = "Request timeout in milliseconds (overrides timeout_ms in the config file).")]
+    timeout_ms: Option<u64>,
+
+    #[arg(long = "retries", global = true, help = "Retries for queries and idempotent commands (overrides retries in the config file).")]
+    retries: Option<u32>,
+
+    #[arg(long = "retry-backoff-ms", global = true, help = "Delay before the first retry in milliseconds, doubled per retry (overrides retry_backoff_ms).")]
+    retry_backoff_ms: Option<u64>,
+
+    #[arg(short = 'o', long = "output", value_enum, global = true, help = "Output format for status and summary commands (default: table, or output in the config file).")]
+    output: Option<OutputFormat>,
+
+    #[arg(long = "config", global = true, help = "Read this config file instead of searching the default locations (or set IZONE_CONFIG).")]
+    config: Option<PathBuf>,
+
+    #[arg(long = "profile", global = true, help = "Use the [profiles.<name>] table from the config file (or set IZONE_PROFILE).")]
+    profile: Option<String>,
 
     #[command(subcommand)]
     command: Commands,
 }
 
-#[derive(clap::Subcommand, Debug)]
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<LogLevel> for LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Off => LevelFilter::Off,
+            LogLevel::Error => LevelFilter::Error,
+            LogLevel::Warn => LevelFilter::Warn,
+            LogLevel::Info => LevelFilter::Info,
+            LogLevel::Debug => LevelFilter::Debug,
+            LogLevel::Trace => LevelFilter::Trace,
+        }
+    }
+}
+
+#[derive(clap::Subcommand, Debug, Clone)]
 enum Commands {
     /// Turn on the entire AC system.
     On,
@@ -64,9 +118,133 @@ enum Commands {
     /// Ventilation system control (ventilation|vent)
     #[clap(name = "ventilation", alias = "vent")]
     Ventilation(VentilationArgs),
+    /// Start the lock timer: the system locks after N days until the keycode is entered.
+    Lock {
+        #[arg(long, help = "Days until the system locks (1-365)")]
+        days: u16,
+        #[arg(long, help = "Keycode to unlock with (up to 6 digits); keeps the current keycode if omitted")]
+        code: Option<String>,
+    },
+    /// Unlock the system and stop the lock timer.
+    Unlock {
+        #[arg(long, help = "Keycode (up to 6 digits)")]
+        code: String,
+    },
+    /// Turn iSave on or off, or show its state.
+    #[clap(name = "isave")]
+    Isave(IsaveArgs),
+    /// Show the AC unit fault history (last 11 faults).
+    Faults,
+    /// List the controller hardware and module firmware versions.
+    Devices,
+    /// Temperzone outdoor unit diagnostics (temperzone|tz)
+    #[clap(name = "temperzone", alias = "tz")]
+    Temperzone(TemperzoneArgs),
+    /// Universal (gas heat) controller settings.
+    #[clap(name = "gasheat")]
+    Gasheat(GasheatArgs),
+    /// Find iZone bridges on the local network (UDP broadcast).
+    Discover(DiscoverArgs),
+    /// Manage controller profiles from the config file.
+    Profile(ProfileArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+struct TemperzoneArgs {
+    #[command(subcommand)]
+    action: TemperzoneAction,
+}
+
+#[derive(clap::Subcommand, Debug, Clone)]
+enum TemperzoneAction {
+    /// Temperatures, pressures, superheat, valve positions and digital I/O. (status|s)
+    #[clap(name = "status", alias = "s")]
+    Status,
+    /// Running hours, cycle counts, protection trips and sensor faults.
+    History,
+    /// Show the unit settings (setpoints, fan and mode type, quiet mode), or change them.
+    Config {
+        #[arg(long = "heat-setpoint", help = "Heat mode setpoint in Celsius (30.0-40.0)")]
+        heat_setpoint: Option<f32>,
+        #[arg(long = "cool-setpoint", help = "Cool mode setpoint in Celsius (5.0-15.0)")]
+        cool_setpoint: Option<f32>,
+        #[arg(long = "fan-type", help = "Fan type: variable or 3-speed")]
+        fan_type: Option<String>,
+        #[arg(long = "mode-type", help = "Mode type: no-expansion, single-expansion, series-expansion or dry-mode")]
+        mode_type: Option<String>,
+        #[arg(long, help = "Outdoor fan quiet mode: true or false")]
+        quiet: Option<bool>,
+    },
+}
+
+#[derive(Args, Debug, Clone)]
+struct IsaveArgs {
+    #[command(subcommand)]
+    action: IsaveAction,
 }
 
-#[derive(Args, Debug)]
+#[derive(clap::Subcommand, Debug, Clone)]
+enum IsaveAction {
+    /// Turn iSave on.
+    On,
+    /// Turn iSave off.
+    Off,
+    /// Show whether iSave is on.
+    Status,
+}
+
+#[derive(Args, Debug, Clone)]
+struct GasheatArgs {
+    #[command(subcommand)]
+    action: GasheatAction,
+}
+
+#[derive(clap::Subcommand, Debug, Clone)]
+enum GasheatAction {
+    /// Show the unit type, run and anti-cycle times, staging and fan cycling.
+    Show,
+    /// Change the settings; fields not given keep their current value.
+    Set {
+        #[arg(long = "type", help = "Unit type, by name (e.g. 1-gas-heat, heat-only) or number (0-13)")]
+        gas_type: Option<String>,
+        #[arg(long = "min-run", help = "Minimum run time in minutes (2-10)")]
+        min_run_time: Option<u8>,
+        #[arg(long = "anticycle", help = "Anti-cycle time in minutes (2-10)")]
+        anticycle_time: Option<u8>,
+        #[arg(long = "stage-offset", help = "Second stage offset in Celsius (2.0-5.0)")]
+        stage_offset: Option<f32>,
+        #[arg(long = "stage-delay", help = "Second stage delay in minutes (5-15)")]
+        stage_delay: Option<u8>,
+        #[arg(long = "cycle-fan-cool", help = "Cool mode fan: true cycles with the unit, false runs continuously")]
+        cycle_fan_cool: Option<bool>,
+        #[arg(long = "cycle-fan-heat", help = "Heat mode fan: true cycles with the unit, false runs continuously")]
+        cycle_fan_heat: Option<bool>,
+    },
+}
+
+#[derive(Args, Debug, Clone)]
+struct ProfileArgs {
+    #[command(subcommand)]
+    action: ProfileAction,
+}
+
+#[derive(clap::Subcommand, Debug, Clone)]
+enum ProfileAction {
+    /// List the configured profiles. (list|ls)
+    #[clap(name = "list", alias = "ls")]
+    List,
+}
+
+#[derive(Args, Debug, Clone)]
+struct DiscoverArgs {
+    #[arg(long, default_value = izone::discovery::BROADCAST_TARGET, help = "Where to send the discovery request (IP:PORT).")]
+    target: String,
+
+    #[arg(long = "wait-ms", default_value_t = izone::discovery::DEFAULT_DISCOVERY_WAIT_MS, help = "How long to wait for replies in milliseconds.")]
+    wait_ms: u64,
+}
+
+#[derive(Args, Debug, Clone)]
 struct ZoneArgs {
     /// The name of the zone (e.g., "kitchen", "work"). Leave empty for summary of all zones.
     #[arg(help = "The name of the zone (e.g., \"kitchen\", \"work\"). Leave empty for summary of all zones.")]
@@ -76,7 +254,7 @@ struct ZoneArgs {
     action: ZoneAction,
 }
 
-#[derive(clap::Subcommand, Debug)]
+#[derive(clap::Subcommand, Debug, Clone)]
 enum ZoneAction {
     /// Get detailed status for the zone. (status|s)
     #[clap(name = "status", aliases = &["s"])]
@@ -159,15 +337,37 @@ enum ZoneAction {
         #[arg(help = "Area in square meters")]
         area: u8,
     },
+    /// Change the zone control type and sensor (reads the zone back to confirm).
+    #[clap(name = "set-type")]
+    SetType {
+        #[arg(help = "Zone type: open-close, constant or auto")]
+        zone_type: String,
+        #[arg(long = "constant-no", help = "Constant number (1 to NoOfConst), required for constant zones")]
+        constant_no: Option<u8>,
+        #[arg(long, help = "Sensor: ccts, csm, czco, crfs, cs or none")]
+        sensor: String,
+    },
+    /// Pair a wireless sensor and wait for it to report in.
+    #[clap(name = "pair-sensor")]
+    PairSensor {
+        #[arg(long, help = "Pair a CRFS2 sensor to this zone (Crfs2SensorPair) instead of generic RfPair")]
+        crfs2: bool,
+        #[arg(long = "tx-interval", help = "CRFS2 transmit interval in seconds: 2, 4, 8, 16, 32, 64, 128 or 256")]
+        tx_interval: Option<u16>,
+        #[arg(long, default_value_t = 120, help = "Seconds to wait for the sensor to report in")]
+        wait: u64,
+        #[arg(long, help = "Pair even though the zone already has a sensor reporting in")]
+        force: bool,
+    },
 }
 
-#[derive(Args, Debug)]
+#[derive(Args, Debug, Clone)]
 struct ModeActionWrapper {
     #[command(subcommand)]
     action: ModeArgs,
 }
 
-#[derive(clap::Subcommand, Debug)]
+#[derive(clap::Subcommand, Debug, Clone)]
 enum ModeArgs {
     /// Set the system mode to Auto.
     #[clap(name = "auto", aliases = &["0", "a"])]
@@ -187,14 +387,14 @@ enum ModeArgs {
 }
 
 // Updated: FanActionWrapper for the 'fan' command
-#[derive(Args, Debug)]
+#[derive(Args, Debug, Clone)]
 struct FanActionWrapper {
     #[command(subcommand)]
     action: FanArgs,
 }
 
 // Updated: FanArgs enum for fan subcommands with aliases
-#[derive(clap::Subcommand, Debug)]
+#[derive(clap::Subcommand, Debug, Clone)]
 enum FanArgs {
     /// Set the system fan speed to Auto. (or 0)
     #[clap(name = "auto", aliases = &["0", "Auto", "a"])]
@@ -208,10 +408,13 @@ enum FanArgs {
     /// Set the system fan speed to High. (or 3)
     #[clap(name = "high", aliases = &["3", "Hi", "hi", "High", "h"])]
     High,
+    /// Set the system fan speed to Top, on 4-speed fans. (or 5)
+    #[clap(name = "top", aliases = &["5", "Top", "t"])]
+    Top,
 }
 
 // New: ScheduleArgs for the 'schedule' command
-#[derive(Args, Debug)]
+#[derive(Args, Debug, Clone)]
 struct ScheduleArgs {
     /// The index of the schedule (0-7).
     #[arg(short = 'i', long = "index", help = "The index of the schedule (0-7).")]
@@ -222,7 +425,7 @@ struct ScheduleArgs {
 }
 
 // New: ScheduleAction enum for schedule subcommands
-#[derive(clap::Subcommand, Debug)]
+#[derive(clap::Subcommand, Debug, Clone)]
 enum ScheduleAction {
     /// Get status for a specific schedule, or a summary of all favourites / schedules if no index is provided.
     Status,
@@ -270,14 +473,27 @@ enum ScheduleAction {
 }
 
 // Config command arguments
-#[derive(Args, Debug)]
+#[derive(Args, Debug, Clone)]
 struct ConfigArgs {
     #[command(subcommand)]
     action: ConfigAction,
 }
 
-#[derive(clap::Subcommand, Debug)]
+#[derive(clap::Subcommand, Debug, Clone)]
 enum ConfigAction {
+    /// Show the effective config and the file it was loaded from.
+    Show,
+    /// Check the config file strictly, reporting problems with line numbers.
+    Check,
+    /// Write a starter config file (to --config, or ~/.config/izone/config.toml).
+    Init {
+        #[arg(long, help = "Fill in izone_ip and zone names from the first bridge that answers discovery")]
+        discover: bool,
+        #[arg(long, default_value = izone::discovery::BROADCAST_TARGET, help = "Where to send the discovery request (IP:PORT)")]
+        target: String,
+        #[arg(long, help = "Overwrite an existing file")]
+        force: bool,
+    },
     /// Set system setpoint temperature (15.0-30.0°C).
     #[clap(name = "set-setpoint")]
     SetSetpoint {
@@ -306,6 +522,68 @@ enum ConfigAction {
         #[arg(help = "Months (0=disabled, 3, 6, or 12)")]
         months: u8,
     },
+    /// Change the wireless sensor RF channel (1-8, reads the system back to confirm).
+    #[clap(name = "rf-channel")]
+    RfChannel {
+        #[arg(help = "Channel (1-8)")]
+        channel: u8,
+    },
+    /// Choose what controls the unit: the return air sensor, the master zone or the zones.
+    #[clap(name = "return-air-sensor")]
+    ReturnAirSensor {
+        #[arg(help = "Control source: ras, master or zones")]
+        source: String,
+    },
+    /// Set the zone that controls the unit when the control source is master.
+    #[clap(name = "master-zone")]
+    MasterZone {
+        #[arg(help = "Zone name or index")]
+        zone: String,
+    },
+    /// Show the two tag lines, or set them (up to 31 characters each).
+    Tag {
+        #[arg(long, help = "Tag line 1")]
+        line1: Option<String>,
+        #[arg(long, help = "Tag line 2")]
+        line2: Option<String>,
+    },
+    /// Change the system configuration password (prompts without echo).
+    Password {
+        #[arg(long, help = "Read the new password from the first line of stdin instead of prompting")]
+        stdin: bool,
+    },
+    /// Set the number of installed zones (installer setting, needs --force).
+    #[clap(name = "zone-count")]
+    ZoneCount {
+        #[arg(help = "Number of zones (1-14)")]
+        zones: u8,
+        #[arg(long, help = "Apply the change (without it, only the warnings are shown)")]
+        force: bool,
+    },
+    /// Set the number of constants (installer setting, needs --force).
+    #[clap(name = "constant-count")]
+    ConstantCount {
+        #[arg(help = "Number of constants (0 to the number of zones)")]
+        constants: u8,
+        #[arg(long, help = "Apply the change (without it, only the warnings are shown)")]
+        force: bool,
+    },
+    /// Show which functions iZone and the AC unit's own controller control, or set one.
+    Linkage {
+        #[command(subcommand)]
+        action: Option<LinkageAction>,
+    },
+    /// Show the fan auto settings and the fan speeds they allow, or change them.
+    #[clap(name = "fan-auto")]
+    FanAuto {
+        #[command(subcommand)]
+        action: Option<FanAutoAction>,
+    },
+    /// Show the installer and display flags (auto off, LEDs, temperature display), or set one.
+    Panel {
+        #[command(subcommand)]
+        action: Option<PanelAction>,
+    },
     /// Reset a warning (e.g., filter).
     #[clap(name = "reset-warning")]
     ResetWarning {
@@ -370,14 +648,64 @@ enum ConfigAction {
     },
 }
 
+#[derive(clap::Subcommand, Debug, Clone)]
+enum LinkageAction {
+    /// Enable or disable one direction of one function.
+    Set {
+        #[arg(help = "izone (iZone controls the unit) or unit (the unit controls iZone)")]
+        direction: String,
+        #[arg(help = "on-off, mode, fan or setpoint")]
+        function: String,
+        #[arg(action = clap::ArgAction::Set, help = "true or false")]
+        enable: bool,
+    },
+}
+
+#[derive(clap::Subcommand, Debug, Clone)]
+enum PanelAction {
+    /// Enable or disable one setting.
+    Set {
+        #[arg(help = "auto-off, room-temp, wired-leds, hide-induct or show-actual-temps")]
+        setting: String,
+        #[arg(action = clap::ArgAction::Set, help = "true or false")]
+        enable: bool,
+    },
+}
+
+#[derive(clap::Subcommand, Debug, Clone)]
+enum FanAutoAction {
+    /// Enable or disable the fan auto function (FanAutoEn).
+    Enable {
+        #[arg(action = clap::ArgAction::Set, help = "true or false")]
+        enable: bool,
+    },
+    /// Set the AC unit fan type (FanAutoType).
+    Type {
+        #[arg(help = "2-speed, 3-speed, variable or 4-speed")]
+        fan_type: String,
+    },
+    /// Set the fan airflow rating (FanCapacity).
+    Capacity {
+        #[arg(help = "Fan airflow rating")]
+        airflow: u16,
+    },
+    /// Set the AC unit capacity in kW (FanUnitCapacity).
+    #[clap(name = "unit-capacity")]
+    UnitCapacity {
+        #[arg(help = "AC unit capacity in kW")]
+        kw: u16,
+    },
+}
+
 // Coolbreeze command arguments
-#[derive(Args, Debug)]
+#[derive(Args, Debug, Clone)]
 struct CoolbreezeArgs {
     #[command(subcommand)]
     action: CoolbreezeAction,
 }
 
-#[derive(clap::Subcommand, Debug)]
+#[derive(clap::Subcommand, Debug, Clone)]
+#[allow(clippy::enum_variant_names)] // Every Coolbreeze action is a setter
 enum CoolbreezeAction {
     /// Set fan speed (1-100%).
     #[clap(name = "set-fan-speed")]
@@ -490,13 +818,13 @@ enum CoolbreezeAction {
 }
 
 // Ventilation command arguments
-#[derive(Args, Debug)]
+#[derive(Args, Debug, Clone)]
 struct VentilationArgs {
     #[command(subcommand)]
     action: VentilationAction,
 }
 
-#[derive(clap::Subcommand, Debug)]
+#[derive(clap::Subcommand, Debug, Clone)]
 enum VentilationAction {
     /// Set humidity setpoint (5-95%).
     #[clap(name = "set-rh-setpoint")]
@@ -552,25 +880,227 @@ enum VentilationAction {
 fn main() {
     let cli = Cli::parse();
 
-    unsafe {
-        constants::VERBOSE = cli.verbose;
+    if let Err(e) = init_logging(&cli) {
+        report_error(&e);
+        exit(exit_code(&e));
+    }
+
+    if let Err(e) = start(cli) {
+        report_error(&e);
+        exit(exit_code(&e));
+    }
+}
+
+/// Loads the config, applies the profile and command-line overrides, then
+/// runs the command.
+fn start(cli: Cli) -> Result<(), IzoneError> {
+    let profile_name = cli
+        .profile
+        .clone()
+        .or_else(|| std::env::var("IZONE_PROFILE").ok().filter(|name| !name.is_empty()));
+    let config_path = cli
+        .config
+        .clone()
+        .or_else(|| std::env::var_os("IZONE_CONFIG").filter(|path| !path.is_empty()).map(PathBuf::from));
+
+    // These work on the file itself, so they run even when it does not parse
+    if let Commands::Config(ConfigArgs { action }) = &cli.command {
+        match action {
+            ConfigAction::Check => return config_file::check_config(config_path.as_deref()),
+            ConfigAction::Init { discover, target, force } => {
+                return config_file::init_config(config_path.as_deref(), *discover, target, *force);
+            }
+            _ => {}
+        }
+    }
+
+    let constants::LoadedConfig { config, path: config_source, .. } = constants::load_config(config_path.as_deref())?;
+
+    // Listing profiles needs every profile, not just the selected one
+    if let Commands::Profile(ProfileArgs { action: ProfileAction::List }) = &cli.command {
+        let output = output_format(cli.output, config.output.as_deref())?;
+        return profile::list_profiles(&config, profile_name.as_deref(), output);
+    }
+
+    let mut config = config.select_profile(profile_name.as_deref())?;
+    let env_overrides = config.apply_env()?;
+    config.timeout_ms = cli.timeout_ms.or(config.timeout_ms);
+    config.retries = cli.retries.or(config.retries);
+    config.retry_backoff_ms = cli.retry_backoff_ms.or(config.retry_backoff_ms);
+    let output = output_format(cli.output, config.output.as_deref())?;
+    if let Some(name) = &config.active_profile {
+        log::debug!("Using profile '{}'", name);
+    }
+
+    if let Commands::Config(ConfigArgs { action: ConfigAction::Show }) = &cli.command {
+        return config_file::show_config(&config, config_source.as_deref(), &env_overrides, output);
     }
 
-    let client = Client::new();
+    // Discovery talks UDP and must work before any bridge is configured
+    if let Commands::Discover(args) = &cli.command {
+        return discover::discover_bridges(&args.target, args.wait_ms, config.active_profile.as_deref(), output);
+    }
+
+    // The CLI always checks the lock, so a locked system is reported instead of ignoring the command
+    let options = ClientOptions { check_lock: true, ..config.client_options() };
+    let client = IzoneClient::with_options(config.resolve_izone_ip()?, options.clone())?;
+    match run(cli.clone(), &config, &client, output) {
+        // A cached "auto" bridge that no longer answers has probably moved; find it again.
+        // Only rerun while nothing was sent, since a command may have been applied before it failed.
+        Err(error)
+            if config.izone_ip_is_auto()
+                && !client.command_sent()
+                && matches!(&error, IzoneError::Connection { url, .. } if url.starts_with(client.base_url())) =>
+        {
+            log::warn!("Bridge {} did not answer, searching the network again.", client.base_url());
+            let url = config.rediscover_izone_ip()?;
+            if url == client.base_url() {
+                return Err(error);
+            }
+            log::warn!("Found the bridge at {}.", url);
+            let client = IzoneClient::with_options(url, options)?;
+            run(cli, &config, &client, output)
+        }
+        result => result,
+    }
+}
+
+/// `--output`, falling back to the `output` config key, then table.
+fn output_format(flag: Option<OutputFormat>, configured: Option<&str>) -> Result<OutputFormat, IzoneError> {
+    match (flag, configured) {
+        (Some(format), _) => Ok(format),
+        (None, Some(name)) => <OutputFormat as clap::ValueEnum>::from_str(name, true).map_err(|_| {
+            IzoneError::validation(format!("Invalid output format '{}' (from the config file or IZONE_OUTPUT). Use table, json, yaml or csv.", name))
+        }),
+        (None, None) => Ok(OutputFormat::Table),
+    }
+}
 
+/// Reports an error on stderr in the formats described in ERROR_MESSAGES.md.
+fn report_error(error: &IzoneError) {
+    match error {
+        IzoneError::Validation(message) => eprintln!("{}", format!("Error: {}", message).red()),
+        _ => eprintln!("{}", error.to_string().red()),
+    }
+
+    if let IzoneError::Parse { body, .. } = error {
+        log::debug!("Raw response: {}", body);
+    }
+}
+
+/// Sets up logging. Precedence: --log-level, then -v/-vv, then IZONE_LOG
+/// (env_logger filter syntax), defaulting to warnings only.
+fn init_logging(cli: &Cli) -> Result<(), IzoneError> {
+    let mut builder = env_logger::Builder::new();
+    builder.filter_level(LevelFilter::Warn);
+    builder.parse_env(env_logger::Env::new().filter("IZONE_LOG"));
+
+    let level = match (cli.log_level, cli.verbose) {
+        (Some(level), _) => Some(level.into()),
+        (None, 0) => None,
+        (None, 1) => Some(LevelFilter::Debug),
+        (None, _) => Some(LevelFilter::Trace),
+    };
+    if let Some(level) = level {
+        builder.filter_module("izone", level);
+    }
+
+    if let Some(path) = &cli.log_file {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| IzoneError::validation(format!("Cannot open log file '{}': {}", path.display(), e)))?;
+        builder.target(env_logger::Target::Pipe(Box::new(file)));
+    } else {
+        builder.target(env_logger::Target::Stderr);
+    }
+
+    builder.format_timestamp_millis().init();
+    Ok(())
+}
+
+/// Process exit code for each error kind, so scripts can tell failures apart.
+fn exit_code(error: &IzoneError) -> i32 {
+    match error {
+        IzoneError::Validation(_) => 2,
+        IzoneError::Connection { .. } => 3,
+        IzoneError::Timeout { .. } => 4,
+        IzoneError::HttpStatus { .. } => 5,
+        IzoneError::Controller { .. } => 6,
+        IzoneError::Parse { .. } => 7,
+        IzoneError::Locked { .. } => 8,
+    }
+}
+
+fn run(cli: Cli, config: &constants::Config, client: &IzoneClient, output: OutputFormat) -> Result<(), IzoneError> {
     match cli.command {
         Commands::On => {
-            system::turn_on_ac(&client);
+            system::turn_on_ac(client)?;
         }
         Commands::Off => {
-            system::turn_off_ac(&client);
+            system::turn_off_ac(client)?;
         }
         Commands::Status => {
-            system::get_system_status(&client);
+            system::get_system_status(client, output)?;
         }
         Commands::ControllerTemp => { // Changed from SystemTemp
-            system::get_system_temperature(&client);
+            system::get_system_temperature(client)?;
+        }
+        Commands::Faults => {
+            faults::list_faults(client, output)?;
+        }
+        Commands::Lock { days, code } => {
+            system::lock_system(client, days, code.as_deref())?;
+        }
+        Commands::Unlock { code } => {
+            system::unlock_system(client, &code)?;
         }
+        Commands::Isave(args) => match args.action {
+            IsaveAction::On => system::set_isave(client, true)?,
+            IsaveAction::Off => system::set_isave(client, false)?,
+            IsaveAction::Status => system::get_isave_status(client)?,
+        },
+        Commands::Devices => {
+            devices::list_devices(client, output)?;
+        }
+        Commands::Temperzone(args) => match args.action {
+            TemperzoneAction::Status => temperzone::get_temperzone_status(client, output)?,
+            TemperzoneAction::History => temperzone::get_temperzone_history(client, output)?,
+            TemperzoneAction::Config { heat_setpoint, cool_setpoint, fan_type, mode_type, quiet } => {
+                let changes = temperzone::TemperzoneConfigChanges {
+                    heat_setpoint,
+                    cool_setpoint,
+                    fan_type: fan_type.as_deref(),
+                    mode_type: mode_type.as_deref(),
+                    quiet,
+                };
+                temperzone::temperzone_config(client, changes, output)?
+            }
+        },
+        Commands::Gasheat(args) => match args.action {
+            GasheatAction::Show => gasheat::show_gas_heat(client, output)?,
+            GasheatAction::Set {
+                gas_type,
+                min_run_time,
+                anticycle_time,
+                stage_offset,
+                stage_delay,
+                cycle_fan_cool,
+                cycle_fan_heat,
+            } => {
+                let changes = gasheat::GasHeatChanges {
+                    gas_type: gas_type.as_deref(),
+                    min_run_time,
+                    anticycle_time,
+                    stage_offset,
+                    stage_delay,
+                    cycle_fan_cool,
+                    cycle_fan_heat,
+                };
+                gasheat::set_gas_heat(client, changes, output)?
+            }
+        },
         Commands::Mode(mode_wrapper) => {
             let mode_string = match mode_wrapper.action {
                 ModeArgs::Auto => "auto",
@@ -579,7 +1109,7 @@ fn main() {
                 ModeArgs::Vent => "vent",
                 ModeArgs::Dry => "dry",
             };
-            system::set_system_mode(&client, mode_string);
+            system::set_system_mode(client, mode_string)?;
         }
         Commands::Fan(fan_wrapper) => { // New: Handle fan command
             let fan_speed_string = match fan_wrapper.action {
@@ -587,161 +1117,158 @@ fn main() {
                 FanArgs::Low => "low",
                 FanArgs::Medium => "medium",
                 FanArgs::High => "high",
+                FanArgs::Top => "top",
             };
-            system::set_system_fan(&client, fan_speed_string);
+            system::set_system_fan(client, fan_speed_string)?;
         }
         Commands::Zone(args) => {
+            let zone_map = ZoneMap::load(config.zones.as_ref(), client)?;
             match args.action {
                 ZoneAction::Status => {
                     if let Some(zone_name) = args.name {
-                        zones::control_zone(&client, &zone_name.to_lowercase(), "status", None);
+                        zones::get_zone_status(client, &zone_map, &zone_name.to_lowercase(), output)?;
                     } else {
-                        eprintln!("Error: 'izone zone status' requires a zone name. Did you mean 'izone zone summary'?");
-                        exit(1);
+                        return Err(IzoneError::validation("'izone zone status' requires a zone name. Did you mean 'izone zone summary'?"));
                     }
                 }
                 ZoneAction::Temp => {
                     if let Some(zone_name) = args.name {
-                        zones::control_zone(&client, &zone_name.to_lowercase(), "temp", None);
+                        zones::control_zone(client, &zone_map, &zone_name.to_lowercase(), "temp", None)?;
                     } else {
-                        eprintln!("Error: 'izone zone temp' requires a zone name.");
-                        exit(1);
+                        return Err(IzoneError::validation("'izone zone temp' requires a zone name."));
                     }
                 }
                 ZoneAction::On => {
                     if let Some(zone_name) = args.name {
-                        zones::control_zone(&client, &zone_name.to_lowercase(), "on", None);
+                        zones::control_zone(client, &zone_map, &zone_name.to_lowercase(), "on", None)?;
                     } else {
-                        eprintln!("Error: 'izone zone on' requires a zone name.");
-                        exit(1);
+                        return Err(IzoneError::validation("'izone zone on' requires a zone name."));
                     }
                 }
                 ZoneAction::Off => {
                     if let Some(zone_name) = args.name {
-                        zones::control_zone(&client, &zone_name.to_lowercase(), "off", None);
+                        zones::control_zone(client, &zone_map, &zone_name.to_lowercase(), "off", None)?;
                     } else {
-                        eprintln!("Error: 'izone zone off' requires a zone name.");
-                        exit(1);
+                        return Err(IzoneError::validation("'izone zone off' requires a zone name."));
                     }
                 }
                 ZoneAction::Open => {
                     if let Some(zone_name) = args.name {
-                        zones::control_zone(&client, &zone_name.to_lowercase(), "open", None);
+                        zones::control_zone(client, &zone_map, &zone_name.to_lowercase(), "open", None)?;
                     } else {
-                        eprintln!("Error: 'izone zone open' requires a zone name.");
-                        exit(1);
+                        return Err(IzoneError::validation("'izone zone open' requires a zone name."));
                     }
                 }
                 ZoneAction::Auto => {
                     if let Some(zone_name) = args.name {
-                        zones::control_zone(&client, &zone_name.to_lowercase(), "auto", None);
+                        zones::control_zone(client, &zone_map, &zone_name.to_lowercase(), "auto", None)?;
                     } else {
-                        eprintln!("Error: 'izone zone auto' requires a zone name.");
-                        exit(1);
+                        return Err(IzoneError::validation("'izone zone auto' requires a zone name."));
                     }
                 }
                 ZoneAction::Override => {
                     if let Some(zone_name) = args.name {
-                        zones::control_zone(&client, &zone_name.to_lowercase(), "override", None);
+                        zones::control_zone(client, &zone_map, &zone_name.to_lowercase(), "override", None)?;
                     } else {
-                        eprintln!("Error: 'izone zone override' requires a zone name.");
-                        exit(1);
+                        return Err(IzoneError::validation("'izone zone override' requires a zone name."));
                     }
                 }
                 ZoneAction::Constant => {
                     if let Some(zone_name) = args.name {
-                        zones::control_zone(&client, &zone_name.to_lowercase(), "constant", None);
+                        zones::control_zone(client, &zone_map, &zone_name.to_lowercase(), "constant", None)?;
                     } else {
-                        eprintln!("Error: 'izone zone constant' requires a zone name.");
-                        exit(1);
+                        return Err(IzoneError::validation("'izone zone constant' requires a zone name."));
                     }
                 }
                 ZoneAction::SetSetpoint { temperature } => {
                     if let Some(zone_name) = args.name {
-                        zones::control_zone(&client, &zone_name.to_lowercase(), "set_setpoint", Some(&temperature));
+                        zones::control_zone(client, &zone_map, &zone_name.to_lowercase(), "set_setpoint", Some(&temperature))?;
                     } else {
-                        eprintln!("Error: 'izone zone set-setpoint' requires a zone name.");
-                        exit(1);
+                        return Err(IzoneError::validation("'izone zone set-setpoint' requires a zone name."));
                     }
                 }
                 ZoneAction::SetMaxAir { percentage } => {
                     if let Some(zone_name) = args.name {
-                        zones::control_zone(&client, &zone_name.to_lowercase(), "set_max_air", Some(&percentage));
+                        zones::control_zone(client, &zone_map, &zone_name.to_lowercase(), "set_max_air", Some(&percentage))?;
                     } else {
-                        eprintln!("Error: 'izone zone set-max-air' requires a zone name.");
-                        exit(1);
+                        return Err(IzoneError::validation("'izone zone set-max-air' requires a zone name."));
                     }
                 }
                 ZoneAction::SetMinAir { percentage } => {
                     if let Some(zone_name) = args.name {
-                        zones::control_zone(&client, &zone_name.to_lowercase(), "set_min_air", Some(&percentage));
+                        zones::control_zone(client, &zone_map, &zone_name.to_lowercase(), "set_min_air", Some(&percentage))?;
                     } else {
-                        eprintln!("Error: 'izone zone set-min-air' requires a zone name.");
-                        exit(1);
+                        return Err(IzoneError::validation("'izone zone set-min-air' requires a zone name."));
                     }
                 }
                 ZoneAction::SetName { new_name } => {
                     if let Some(zone_name) = args.name {
-                        zones::control_zone(&client, &zone_name.to_lowercase(), "set_name", Some(&new_name));
+                        zones::control_zone(client, &zone_map, &zone_name.to_lowercase(), "set_name", Some(&new_name))?;
                     } else {
-                        eprintln!("Error: 'izone zone set-name' requires a zone name.");
-                        exit(1);
+                        return Err(IzoneError::validation("'izone zone set-name' requires a zone name."));
                     }
                 }
                 ZoneAction::Summary => {
                     if args.name.is_some() {
-                        eprintln!("Error: 'izone zone summary' does not take a zone name argument.");
-                        exit(1);
+                        return Err(IzoneError::validation("'izone zone summary' does not take a zone name argument."));
                     }
-                    zones::get_all_zones_summary(&client);
+                    zones::get_all_zones_summary(client, &zone_map, output)?;
                 }
                 ZoneAction::SetBalanceMax { percentage } => {
                     if let Some(zone_name) = args.name {
-                        zones::set_zone_balance_max(&client, &zone_name.to_lowercase(), percentage);
+                        zones::set_zone_balance_max(client, &zone_map, &zone_name.to_lowercase(), percentage)?;
                     } else {
-                        eprintln!("Error: 'izone zone set-balance-max' requires a zone name.");
-                        exit(1);
+                        return Err(IzoneError::validation("'izone zone set-balance-max' requires a zone name."));
                     }
                 }
                 ZoneAction::SetBalanceMin { percentage } => {
                     if let Some(zone_name) = args.name {
-                        zones::set_zone_balance_min(&client, &zone_name.to_lowercase(), percentage);
+                        zones::set_zone_balance_min(client, &zone_map, &zone_name.to_lowercase(), percentage)?;
                     } else {
-                        eprintln!("Error: 'izone zone set-balance-min' requires a zone name.");
-                        exit(1);
+                        return Err(IzoneError::validation("'izone zone set-balance-min' requires a zone name."));
                     }
                 }
                 ZoneAction::SetDamperSkip { enable } => {
                     if let Some(zone_name) = args.name {
-                        zones::set_zone_damper_skip(&client, &zone_name.to_lowercase(), enable);
+                        zones::set_zone_damper_skip(client, &zone_map, &zone_name.to_lowercase(), enable)?;
                     } else {
-                        eprintln!("Error: 'izone zone set-damper-skip' requires a zone name.");
-                        exit(1);
+                        return Err(IzoneError::validation("'izone zone set-damper-skip' requires a zone name."));
                     }
                 }
                 ZoneAction::SetSensorCalibration { calibration } => {
                     if let Some(zone_name) = args.name {
                         let calibration_val = (calibration * 10.0).round() as i8;
-                        zones::set_zone_sensor_calibration(&client, &zone_name.to_lowercase(), calibration_val);
+                        zones::set_zone_sensor_calibration(client, &zone_map, &zone_name.to_lowercase(), calibration_val)?;
                     } else {
-                        eprintln!("Error: 'izone zone set-sensor-calibration' requires a zone name.");
-                        exit(1);
+                        return Err(IzoneError::validation("'izone zone set-sensor-calibration' requires a zone name."));
                     }
                 }
                 ZoneAction::SetBypass { enable } => {
                     if let Some(zone_name) = args.name {
-                        zones::set_zone_bypass(&client, &zone_name.to_lowercase(), enable);
+                        zones::set_zone_bypass(client, &zone_map, &zone_name.to_lowercase(), enable)?;
                     } else {
-                        eprintln!("Error: 'izone zone set-bypass' requires a zone name.");
-                        exit(1);
+                        return Err(IzoneError::validation("'izone zone set-bypass' requires a zone name."));
                     }
                 }
                 ZoneAction::SetArea { area } => {
                     if let Some(zone_name) = args.name {
-                        zones::set_zone_area(&client, &zone_name.to_lowercase(), area);
+                        zones::set_zone_area(client, &zone_map, &zone_name.to_lowercase(), area)?;
                     } else {
-                        eprintln!("Error: 'izone zone set-area' requires a zone name.");
-                        exit(1);
+                        return Err(IzoneError::validation("'izone zone set-area' requires a zone name."));
+                    }
+                }
+                ZoneAction::SetType { zone_type, constant_no, sensor } => {
+                    if let Some(zone_name) = args.name {
+                        zones::set_zone_type(client, &zone_map, &zone_name.to_lowercase(), &zone_type, constant_no, &sensor)?;
+                    } else {
+                        return Err(IzoneError::validation("'izone zone set-type' requires a zone name."));
+                    }
+                }
+                ZoneAction::PairSensor { crfs2, tx_interval, wait, force } => {
+                    if let Some(zone_name) = args.name {
+                        zones::pair_zone_sensor(client, &zone_map, &zone_name.to_lowercase(), crfs2, tx_interval, wait, force)?;
+                    } else {
+                        return Err(IzoneError::validation("'izone zone pair-sensor' requires a zone name."));
                     }
                 }
             }
@@ -751,17 +1278,16 @@ fn main() {
             match args.action {
                 ScheduleAction::Status => {
                     if let Some(index) = args.index {
-                        schedules::get_schedule_status(&client, index);
+                        schedules::get_schedule_status(client, index, output)?;
                     } else {
-                        schedules::get_all_schedules_summary(&client);
+                        schedules::get_all_schedules_summary(client, output)?;
                     }
                 }
                 ScheduleAction::SetName { new_name } => {
                     if let Some(index) = args.index {
-                        schedules::set_schedule_name(&client, index, &new_name);
+                        schedules::set_schedule_name(client, index, &new_name)?;
                     } else {
-                        eprintln!("{}", "Error: 'izone schedule set-name' requires a schedule index (-i <index>).".red());
-                        exit(1);
+                        return Err(IzoneError::validation("'izone schedule set-name' requires a schedule index (-i <index>)."));
                     }
                 }
                 ScheduleAction::SetTime { start_time, stop_time } => {
@@ -781,53 +1307,44 @@ fn main() {
                             }
                         };
 
-                        let (start_h, start_m) = parse_time(&start_time).unwrap_or_else(|e| {
-                            eprintln!("Error parsing start time: {}", e.red());
-                            exit(1);
-                        });
-                        let (stop_h, stop_m) = parse_time(&stop_time).unwrap_or_else(|e| {
-                            eprintln!("Error parsing stop time: {}", e.red());
-                            exit(1);
-                        });
+                        let (start_h, start_m) = parse_time(&start_time)
+                            .map_err(|e| IzoneError::validation(format!("Error parsing start time: {}", e)))?;
+                        let (stop_h, stop_m) = parse_time(&stop_time)
+                            .map_err(|e| IzoneError::validation(format!("Error parsing stop time: {}", e)))?;
 
-                        schedules::set_schedule_time(&client, index, start_h, start_m, stop_h, stop_m);
+                        schedules::set_schedule_time(client, index, start_h, start_m, stop_h, stop_m)?;
                     } else {
-                        eprintln!("{}", "Error: 'izone schedule set-time' requires a schedule index (-i <index>).".red());
-                        exit(1);
+                        return Err(IzoneError::validation("'izone schedule set-time' requires a schedule index (-i <index>)."));
                     }
                 }
                 ScheduleAction::SetDays { days } => {
                     if let Some(index) = args.index {
-                        schedules::set_schedule_days(&client, index, days);
+                        schedules::set_schedule_days(client, index, days)?;
                     } else {
-                        eprintln!("{}", "Error: 'izone schedule set-days' requires a schedule index (-i <index>).".red());
-                        exit(1);
+                        return Err(IzoneError::validation("'izone schedule set-days' requires a schedule index (-i <index>)."));
                     }
                 }
                 ScheduleAction::SetAc { mode, fan } => {
                     if let Some(index) = args.index {
                         let mode_str = mode.as_deref(); // Convert Option<String> to Option<&str>
                         let fan_str = fan.as_deref(); // Convert Option<String> to Option<&str>
-                        schedules::set_schedule_mode_fan(&client, index, mode_str, fan_str);
+                        schedules::set_schedule_mode_fan(client, index, mode_str, fan_str)?;
                     } else {
-                        eprintln!("{}", "Error: 'izone schedule set-ac' requires a schedule index (-i <index>).".red());
-                        exit(1);
+                        return Err(IzoneError::validation("'izone schedule set-ac' requires a schedule index (-i <index>)."));
                     }
                 }
                 ScheduleAction::Enable => {
                     if let Some(index) = args.index {
-                        schedules::enable_schedule(&client, index);
+                        schedules::enable_schedule(client, index)?;
                     } else {
-                        eprintln!("{}", "Error: 'izone schedule enable' requires a schedule index (-i <index>).".red());
-                        exit(1);
+                        return Err(IzoneError::validation("'izone schedule enable' requires a schedule index (-i <index>)."));
                     }
                 }
                 ScheduleAction::Disable => {
                     if let Some(index) = args.index {
-                        schedules::disable_schedule(&client, index);
+                        schedules::disable_schedule(client, index)?;
                     } else {
-                        eprintln!("{}", "Error: 'izone schedule disable' requires a schedule index (-i <index>).".red());
-                        exit(1);
+                        return Err(IzoneError::validation("'izone schedule disable' requires a schedule index (-i <index>)."));
                     }
                 }
                 ScheduleAction::SetZones { zone_settings } => {
@@ -837,24 +1354,21 @@ fn main() {
                             let parts: Vec<&str> = setting_str.split(':').collect();
                             if parts.len() == 3 {
                                 let zone_name = parts[0].to_string();
-                                let mode_val = parts[1].parse::<u8>().unwrap_or_else(|_| {
-                                    eprintln!("Error: Invalid mode value in '{}'. Must be an integer.", setting_str.red());
-                                    exit(1);
-                                });
-                                let setpoint = parts[2].parse::<u32>().unwrap_or_else(|_| {
-                                    eprintln!("Error: Invalid setpoint value in '{}'. Must be an integer (e.g., 2250 for 22.5).", setting_str.red());
-                                    exit(1);
-                                });
+                                let mode_val = parts[1].parse::<u8>().map_err(|_| {
+                                    IzoneError::validation(format!("Invalid mode value in '{}'. Must be an integer.", setting_str))
+                                })?;
+                                let setpoint = parts[2].parse::<u32>().map_err(|_| {
+                                    IzoneError::validation(format!("Invalid setpoint value in '{}'. Must be an integer (e.g., 2250 for 22.5).", setting_str))
+                                })?;
                                 parsed_zone_settings.push((zone_name, mode_val, setpoint));
                             } else {
-                                eprintln!("Error: Invalid zone setting format '{}'. Expected format: <zone_name>:<mode_val>:<setpoint> (e.g., kitchen:3:2250)", setting_str.red());
-                                exit(1);
+                                return Err(IzoneError::validation(format!("Invalid zone setting format '{}'. Expected format: <zone_name>:<mode_val>:<setpoint> (e.g., kitchen:3:2250)", setting_str)));
                             }
                         }
-                        schedules::set_schedule_zones(&client, index, parsed_zone_settings);
+                        let zone_map = ZoneMap::load(config.zones.as_ref(), client)?;
+                        schedules::set_schedule_zones(client, &zone_map, index, parsed_zone_settings)?;
                     } else {
-                        eprintln!("{}", "Error: 'izone schedule set-zones' requires a schedule index (-i <index>).".red());
-                        exit(1);
+                        return Err(IzoneError::validation("'izone schedule set-zones' requires a schedule index (-i <index>)."));
                     }
                 }
             }
@@ -862,47 +1376,91 @@ fn main() {
         // Config commands
         Commands::Config(args) => {
             match args.action {
+                ConfigAction::Show | ConfigAction::Check | ConfigAction::Init { .. } => {
+                    unreachable!("config show/check/init are handled before connecting")
+                }
                 ConfigAction::SetSetpoint { temperature } => {
-                    system::set_system_setpoint(&client, temperature);
+                    system::set_system_setpoint(client, temperature)?;
                 }
                 ConfigAction::SetSleepTimer { minutes } => {
-                    system::set_sleep_timer(&client, minutes);
+                    system::set_sleep_timer(client, minutes)?;
                 }
                 ConfigAction::SetEconomyLock { enable, min, max } => {
-                    system::set_economy_lock(&client, enable, min, max);
+                    system::set_economy_lock(client, enable, min, max)?;
                 }
                 ConfigAction::SetFilterWarning { months } => {
-                    system::set_filter_warning(&client, months);
+                    system::set_filter_warning(client, months)?;
+                }
+                ConfigAction::RfChannel { channel } => {
+                    system::set_rf_channel(client, channel)?;
+                }
+                ConfigAction::ReturnAirSensor { source } => {
+                    system::set_return_air_sensor(client, &source)?;
+                }
+                ConfigAction::MasterZone { zone } => {
+                    let zone_map = ZoneMap::load(config.zones.as_ref(), client)?;
+                    system::set_master_zone(client, &zone_map, &zone.to_lowercase())?;
+                }
+                ConfigAction::Tag { line1, line2 } => {
+                    system::set_system_tags(client, line1.as_deref(), line2.as_deref())?;
+                }
+                ConfigAction::Password { stdin } => {
+                    system::set_config_password(client, stdin)?;
+                }
+                ConfigAction::ZoneCount { zones, force } => {
+                    system::set_zone_count(client, zones, force)?;
+                }
+                ConfigAction::ConstantCount { constants, force } => {
+                    system::set_constant_count(client, constants, force)?;
+                }
+                ConfigAction::Linkage { action: None } => {
+                    system::get_linkage(client, output)?;
+                }
+                ConfigAction::Linkage { action: Some(LinkageAction::Set { direction, function, enable }) } => {
+                    system::set_linkage(client, &direction, &function, enable)?;
+                }
+                ConfigAction::FanAuto { action } => match action {
+                    None => system::get_fan_auto(client, output)?,
+                    Some(FanAutoAction::Enable { enable }) => system::set_fan_auto_enable(client, enable)?,
+                    Some(FanAutoAction::Type { fan_type }) => system::set_fan_auto_type(client, &fan_type)?,
+                    Some(FanAutoAction::Capacity { airflow }) => system::set_fan_capacity(client, airflow)?,
+                    Some(FanAutoAction::UnitCapacity { kw }) => system::set_fan_unit_capacity(client, kw)?,
+                },
+                ConfigAction::Panel { action: None } => {
+                    system::get_panel(client, output)?;
+                }
+                ConfigAction::Panel { action: Some(PanelAction::Set { setting, enable }) } => {
+                    system::set_panel(client, &setting, enable)?;
                 }
                 ConfigAction::ResetWarning { warning_type } => {
-                    system::reset_warning(&client, &warning_type);
+                    system::reset_warning(client, &warning_type)?;
                 }
                 ConfigAction::SetDamperTime { seconds } => {
-                    system::set_damper_time(&client, seconds);
+                    system::set_damper_time(client, seconds)?;
                 }
                 ConfigAction::SetAutoModeDeadband { deadband } => {
-                    system::set_auto_mode_deadband(&client, deadband);
+                    system::set_auto_mode_deadband(client, deadband)?;
                 }
                 ConfigAction::SetAirflowLock { enable } => {
-                    system::set_airflow_lock(&client, enable);
+                    system::set_airflow_lock(client, enable)?;
                 }
                 ConfigAction::SetAirflowMinLock { enable } => {
-                    system::set_airflow_min_lock(&client, enable);
+                    system::set_airflow_min_lock(client, enable)?;
                 }
                 ConfigAction::SetStaticPressure { level } => {
-                    system::set_static_pressure(&client, level);
+                    system::set_static_pressure(client, level)?;
                 }
                 ConfigAction::SetOpenDampersWhenOff { enable } => {
-                    system::set_open_dampers_when_off(&client, enable);
+                    system::set_open_dampers_when_off(client, enable)?;
                 }
                 ConfigAction::SetScroogeMode { enable } => {
-                    system::set_scrooge_mode(&client, enable);
+                    system::set_scrooge_mode(client, enable)?;
                 }
                 ConfigAction::SetReverseDampers { enable } => {
-                    system::set_reverse_dampers(&client, enable);
+                    system::set_reverse_dampers(client, enable)?;
                 }
                 ConfigAction::SetConstantControlArea { enable, area } => {
-                    system::set_constant_control_by_area(&client, enable, area);
+                    system::set_constant_control_by_area(client, enable, area)?;
                 }
             }
         }
@@ -910,55 +1468,55 @@ fn main() {
         Commands::Coolbreeze(args) => {
             match args.action {
                 CoolbreezeAction::SetFanSpeed { speed } => {
-                    system::set_coolbreeze_fan_speed(&client, speed);
+                    system::set_coolbreeze_fan_speed(client, speed)?;
                 }
                 CoolbreezeAction::SetRhSetpoint { rh } => {
-                    system::set_coolbreeze_rh_setpoint(&client, rh);
+                    system::set_coolbreeze_rh_setpoint(client, rh)?;
                 }
                 CoolbreezeAction::SetPrewash { enable, time } => {
-                    system::set_coolbreeze_prewash(&client, enable, time);
+                    system::set_coolbreeze_prewash(client, enable, time)?;
                 }
                 CoolbreezeAction::SetDrainAfterPrewash { enable } => {
-                    system::set_coolbreeze_drain_after_prewash(&client, enable);
+                    system::set_coolbreeze_drain_after_prewash(client, enable)?;
                 }
                 CoolbreezeAction::SetDrainCycle { enable, period } => {
-                    system::set_coolbreeze_drain_cycle(&client, enable, period);
+                    system::set_coolbreeze_drain_cycle(client, enable, period)?;
                 }
                 CoolbreezeAction::SetPostwash { enable, time } => {
-                    system::set_coolbreeze_postwash(&client, enable, time);
+                    system::set_coolbreeze_postwash(client, enable, time)?;
                 }
                 CoolbreezeAction::SetDrainBeforePostwash { enable } => {
-                    system::set_coolbreeze_drain_before_postwash(&client, enable);
+                    system::set_coolbreeze_drain_before_postwash(client, enable)?;
                 }
                 CoolbreezeAction::SetInverter { enable } => {
-                    system::set_coolbreeze_inverter(&client, enable);
+                    system::set_coolbreeze_inverter(client, enable)?;
                 }
                 CoolbreezeAction::SetResumeLast { enable } => {
-                    system::set_coolbreeze_resume_last(&client, enable);
+                    system::set_coolbreeze_resume_last(client, enable)?;
                 }
                 CoolbreezeAction::SetFanMaxAuto { speed } => {
-                    system::set_coolbreeze_fan_max_auto(&client, speed);
+                    system::set_coolbreeze_fan_max_auto(client, speed)?;
                 }
                 CoolbreezeAction::SetFanMax { speed } => {
-                    system::set_coolbreeze_fan_max(&client, speed);
+                    system::set_coolbreeze_fan_max(client, speed)?;
                 }
                 CoolbreezeAction::SetExhaustMax { speed } => {
-                    system::set_coolbreeze_exhaust_max(&client, speed);
+                    system::set_coolbreeze_exhaust_max(client, speed)?;
                 }
                 CoolbreezeAction::SetExhaustEnable { enable } => {
-                    system::set_coolbreeze_exhaust_enable(&client, enable);
+                    system::set_coolbreeze_exhaust_enable(client, enable)?;
                 }
                 CoolbreezeAction::SetControlSensor { sensor_type } => {
-                    system::set_coolbreeze_control_sensor(&client, &sensor_type);
+                    system::set_coolbreeze_control_sensor(client, &sensor_type)?;
                 }
                 CoolbreezeAction::SetTempCalibration { calibration } => {
-                    system::set_coolbreeze_temp_calibration(&client, calibration);
+                    system::set_coolbreeze_temp_calibration(client, calibration)?;
                 }
                 CoolbreezeAction::SetTempDeadband { deadband } => {
-                    system::set_coolbreeze_temp_deadband(&client, deadband);
+                    system::set_coolbreeze_temp_deadband(client, deadband)?;
                 }
                 CoolbreezeAction::SetAutoFanMaxTime { time } => {
-                    system::set_coolbreeze_auto_fan_max_time(&client, time);
+                    system::set_coolbreeze_auto_fan_max_time(client, time)?;
                 }
             }
         }
@@ -966,30 +1524,35 @@ fn main() {
         Commands::Ventilation(args) => {
             match args.action {
                 VentilationAction::SetRhSetpoint { rh } => {
-                    system::set_ventilation_rh_setpoint(&client, rh);
+                    system::set_ventilation_rh_setpoint(client, rh)?;
                 }
                 VentilationAction::SetVocsSetpoint { vocs } => {
-                    system::set_ventilation_vocs_setpoint(&client, vocs);
+                    system::set_ventilation_vocs_setpoint(client, vocs)?;
                 }
                 VentilationAction::SetEco2Setpoint { eco2 } => {
-                    system::set_ventilation_eco2_setpoint(&client, eco2);
+                    system::set_ventilation_eco2_setpoint(client, eco2)?;
                 }
                 VentilationAction::SetFanStageDelay { delay } => {
-                    system::set_ventilation_fan_stage_delay(&client, delay);
+                    system::set_ventilation_fan_stage_delay(client, delay)?;
                 }
                 VentilationAction::SetCycleFanOff { enable } => {
-                    system::set_ventilation_cycle_fan_off(&client, enable);
+                    system::set_ventilation_cycle_fan_off(client, enable)?;
                 }
                 VentilationAction::SetUseRhControl { enable } => {
-                    system::set_ventilation_use_rh_control(&client, enable);
+                    system::set_ventilation_use_rh_control(client, enable)?;
                 }
                 VentilationAction::SetUseVocsControl { enable } => {
-                    system::set_ventilation_use_vocs_control(&client, enable);
+                    system::set_ventilation_use_vocs_control(client, enable)?;
                 }
                 VentilationAction::SetUseEco2Control { enable } => {
-                    system::set_ventilation_use_eco2_control(&client, enable);
+                    system::set_ventilation_use_eco2_control(client, enable)?;
                 }
             }
         }
+        Commands::Discover(_) | Commands::Profile(_) => {
+            unreachable!("discover and profile are handled before connecting")
+        }
     }
-}
\ No newline at end of file
+
+    Ok(())
+}
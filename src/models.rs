@@ -1,8 +1,39 @@
 // izone/src/models.rs
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
+use std::fmt;
+use crate::api::MASK;
 use crate::helpers::deserialize_int_as_bool;
 
+/// The system configuration password (`Pass`). Debug output and
+/// serialization show `api::MASK` instead of the value; use `expose()` to
+/// compare it.
+#[derive(Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct Password(String);
+
+impl Password {
+    pub fn new(password: impl Into<String>) -> Self {
+        Password(password.into())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Password {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", MASK)
+    }
+}
+
+impl Serialize for Password {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(MASK)
+    }
+}
+
 // Nested structs for SystemV2
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "PascalCase")]
@@ -174,8 +205,9 @@ pub struct SystemV2 {
     pub lock_status: u8,
     #[serde(default)]
     pub lock_on: u8,
+    /// `None` on firmware that predates the fan auto settings.
     #[serde(default)]
-    pub fan_auto_en: u8,
+    pub fan_auto_en: Option<u8>,
     #[serde(default)]
     pub fan_auto_type: u8,
     #[serde(default)]
@@ -237,7 +269,7 @@ pub struct SystemV2 {
     #[serde(default)]
     pub scrooge: u8,
     #[serde(default)]
-    pub pass: String,
+    pub pass: Password,
     #[serde(default)]
     pub cnst_ctrl_area_en: u8,
     #[serde(default)]
@@ -269,9 +301,21 @@ pub struct SystemV2Response {
     pub system_v2: SystemV2,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Zone operating mode (`ZoneMode_e`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZoneMode {
+    Open = 1,
+    Close = 2,
+    Auto = 3,
+    Override = 4,
+    Constant = 5,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct ZonesV2 {
+    #[serde(default)]
+    pub index: u8,
     pub name: String,
     pub mode: u8,
     pub setpoint: u32,
@@ -290,15 +334,15 @@ pub struct ZonesV2 {
     pub master: u8,
     #[serde(rename = "DmpFlt")]
     pub damper_fault: u8,
-    #[serde(rename = "SnsFlt", default)]
+    #[serde(rename = "SnsFlt", alias = "SensorFault", default)]
     pub sensor_fault: u8,
-    #[serde(rename = "DmpSkip", default)]
+    #[serde(rename = "DmpSkip", alias = "DamperSkip", default)]
     pub damper_skip: u8,
     #[serde(default)]
     pub isense: u8,
-    pub calibration: u8,
-    #[serde(rename = "RFSig", default)]
-    pub rf_signal: u8,
+    pub calibration: i16, // x10, -50 to 50
+    #[serde(rename = "RFSig", alias = "RfSignal", default)]
+    pub rf_signal: u8, // RfSignalLevel_e
     #[serde(rename = "BattVolt")]
     pub batt_volt: u8,
     pub area: u32,
@@ -394,4 +438,319 @@ pub struct SchedulesResponseWrapper {
     pub device_type: Option<String>,
     #[serde(rename = "SchedulesV2")]
     pub schedules_v2: SchedulesV2,
-}
\ No newline at end of file
+}
+/// One entry of the AC unit fault history (request type 4).
+///
+/// The controller sends the month and the minute under the same key:
+/// `{"Code":"E 52","D":14,"M":3,"Y":2024,"H":9,"M":41}`. A derived
+/// `Deserialize` would reject the duplicate (or keep only the last value), so
+/// the fields are read in order: the first "M" is the month, the second the
+/// minute. This only works on the raw response text; going through a
+/// `serde_json::Value` first loses the month.
+#[derive(Debug, Serialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub struct AcFault {
+    /// Fault code reported by the AC unit (up to 4 characters).
+    pub code: String,
+    pub day: u8,
+    pub month: u8,
+    /// As sent by the controller, either 2 or 4 digits.
+    pub year: u16,
+    pub hour: u8,
+    pub minute: u8,
+}
+
+impl AcFault {
+    /// Unused history slots have no code.
+    pub fn is_empty(&self) -> bool {
+        self.code.trim().is_empty()
+    }
+
+    /// "YYYY-MM-DD HH:MM", or `None` if the controller sent an impossible date.
+    pub fn timestamp(&self) -> Option<String> {
+        let year = if self.year < 100 { 2000 + self.year } else { self.year };
+        let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+        let days_in_month = match self.month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => return None,
+        };
+        if self.day == 0 || self.day > days_in_month || self.hour > 23 || self.minute > 59 {
+            return None;
+        }
+        Some(format!("{:04}-{:02}-{:02} {:02}:{:02}", year, self.month, self.day, self.hour, self.minute))
+    }
+}
+
+impl<'de> Deserialize<'de> for AcFault {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct AcFaultVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for AcFaultVisitor {
+            type Value = AcFault;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("an AC unit fault entry")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<AcFault, A::Error> {
+                let mut fault = AcFault::default();
+                let mut seen_month = false;
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "Code" => fault.code = map.next_value()?,
+                        "D" => fault.day = map.next_value()?,
+                        "Y" => fault.year = map.next_value()?,
+                        "H" => fault.hour = map.next_value()?,
+                        "M" if !seen_month => {
+                            fault.month = map.next_value()?;
+                            seen_month = true;
+                        }
+                        "M" => fault.minute = map.next_value()?,
+                        _ => {
+                            map.next_value::<serde::de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(fault)
+            }
+        }
+
+        deserializer.deserialize_map(AcFaultVisitor)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct AcUnitFaultHistV2 {
+    #[serde(default)]
+    pub faults: Vec<AcFault>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AcUnitFaultHistResponse {
+    #[serde(rename = "AcUnitFaultHistV2")]
+    pub ac_unit_fault_hist_v2: AcUnitFaultHistV2,
+}
+
+// Temperzone outdoor unit status (request type 5). The values are read from
+// the unit's UC8 controller and passed through unscaled; see `report.rs` for
+// the units.
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct TemperzoneTemps {
+    pub outdoor_coil: i32,
+    pub indoor_coil: i32,
+    pub ambient: i32,
+    pub suction_line: i32,
+    pub discharge_line: i32,
+    pub de_ice_sensor: i32,
+    pub evaporating: i32,
+    pub condensing: i32,
+    pub controller: i32,
+    pub suction_side_superheat: i32,
+    pub discharge_side_superheat: i32,
+    pub vacant: i32,
+    pub suction_line_pressure: i32,
+    pub discharge_line_pressure: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct TemperzoneOutputs {
+    pub outdoor_fan_speed: i32,
+    pub indoor_fan_speed: i32,
+    pub exv1_position: i32,
+    pub exv2_position: i32,
+    pub unit_capacity: i32,
+    pub unit_mode: i32,
+    pub digital_outputs: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct TemperzoneThermostats {
+    pub indoor_unit_coil_temperature1: i32,
+    pub indoor_unit_suction_line_temperature1: i32,
+    pub indoor_unit_coil_temperature2: i32,
+    pub indoor_unit_suction_line_temperature2: i32,
+    pub supply_air_temperature: i32,
+    pub return_air_temperature: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct TemperzoneUc8 {
+    pub id_code: u32,
+    pub software_version: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct TemperzoneHistory8 {
+    pub modbus_address: u32,
+    pub reserved1: u32,
+    pub total_running_hours: u32,
+    pub total_running_minutes: u32,
+    pub total_cooling_cycles_made: u32,
+    pub total_heating_cycles_made: u32,
+    pub total_deice_cycles_made: u32,
+    pub hp_trip_events: u32,
+    pub lp_trip_events: u32,
+    pub frost_protection_events: u32,
+    pub freeze_protection_events: u32,
+    pub high_temperature_protection_events: u32,
+    pub high_suction_line_temperature_protection_events: u32,
+    pub overload_protection_events: u32,
+    // "Dischanrge" is spelled this way by the controller.
+    #[serde(rename = "LowDischanrgeSuperheatProtectionEvents")]
+    pub low_discharge_superheat_protection_events: u32,
+    #[serde(rename = "HighDischanrgeSuperheatProtectionEvents")]
+    pub high_discharge_superheat_protection_events: u32,
+    pub number_of_power_on_reset_events: u32,
+    pub reserved2: u32,
+    pub reserved3: u32,
+    pub reserved4: u32,
+    pub indoor_coil_temperature_sensor_faults: u32,
+    pub outdoor_coil_temperature_sensor_faults: u32,
+    pub outdoor_ambient_temperature_faults: u32,
+    pub discharge_line_temperature_sensor_faults: u32,
+    pub suction_line_temperature_sensor_faults: u32,
+    pub deice_temperature_sensor_faults: u32,
+    pub high_pressure_sensor_faults: u32,
+    pub low_pressure_sensor_faults: u32,
+    pub high_board_temperature_faults: u32,
+    pub reverse_cycle_valve_faults: u32,
+    pub iuc_communication_faults: u32,
+    pub iuc_faults: u32,
+    pub inverter_faults: u32,
+    pub compressor_out_of_envelope_faults: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct TemperzoneInputStatus {
+    pub inputs: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct TemperzoneOutputStatus {
+    pub outputs: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct TemperzoneTimers {
+    pub minimum_on_off_time: u32,
+    pub minimum_off_on_time: u32,
+    pub minimum_on_on_time: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct TemperzoneInfoV2 {
+    pub temps: TemperzoneTemps,
+    pub outputs: TemperzoneOutputs,
+    pub thermostats: TemperzoneThermostats,
+    #[serde(rename = "UC8")]
+    pub uc8: TemperzoneUc8,
+    pub history8: TemperzoneHistory8,
+    pub input_status: TemperzoneInputStatus,
+    pub output_status: TemperzoneOutputStatus,
+    pub timers: TemperzoneTimers,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TemperzoneInfoResponse {
+    #[serde(rename = "TemperzoneInfoV2")]
+    pub temperzone_info_v2: TemperzoneInfoV2,
+}
+
+/// A connected module and its firmware version (request type 6).
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct Firmware {
+    pub device: String,
+    /// Empty if the controller listed the device without a version.
+    pub version: String,
+}
+
+impl Firmware {
+    /// Parses the comma-separated `Fmw` string. Entries may carry their version
+    /// ("C300:1.2", "C300=1.2", "C300 1.2") or be followed by it as the next
+    /// item ("C300,1.2"); a bare device name gets an empty version.
+    pub fn parse_list(list: &str) -> Vec<Firmware> {
+        let looks_like_version = |s: &str| {
+            let s = s.strip_prefix(['v', 'V']).unwrap_or(s);
+            s.starts_with(|c: char| c.is_ascii_digit())
+        };
+
+        let mut items = list.split(',').map(str::trim).filter(|s| !s.is_empty()).peekable();
+        let mut firmware = Vec::new();
+        while let Some(item) = items.next() {
+            let entry = match item.split_once([':', '=', ' ']) {
+                Some((device, version)) => Firmware {
+                    device: device.trim().to_string(),
+                    version: version.trim().to_string(),
+                },
+                None => {
+                    let version = items.next_if(|next| looks_like_version(next)).unwrap_or_default();
+                    Firmware { device: item.to_string(), version: version.to_string() }
+                }
+            };
+            firmware.push(entry);
+        }
+        firmware
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FirmwareListResponse {
+    #[serde(rename = "Fmw", default)]
+    pub fmw: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ac_fault_reads_the_duplicate_m_key_as_month_then_minute() {
+        let body = r#"{
+            "AirStreamDeviceUId": "001EC0028041",
+            "DeviceType": "ASH",
+            "AcUnitFaultHistV2": {
+                "Faults": [
+                    {"Code": "E4", "D": 17, "M": 3, "Y": 2024, "H": 14, "M": 45},
+                    {"Code": "", "D": 0, "M": 0, "Y": 0, "H": 0, "M": 0}
+                ]
+            }
+        }"#;
+        let response: AcUnitFaultHistResponse = serde_json::from_str(body).unwrap();
+        let faults = response.ac_unit_fault_hist_v2.faults;
+        assert_eq!(faults.len(), 2);
+
+        let fault = &faults[0];
+        assert_eq!(fault.code, "E4");
+        assert_eq!(fault.day, 17);
+        assert_eq!(fault.month, 3);
+        assert_eq!(fault.year, 2024);
+        assert_eq!(fault.hour, 14);
+        assert_eq!(fault.minute, 45);
+        assert_eq!(fault.timestamp().as_deref(), Some("2024-03-17 14:45"));
+
+        assert!(faults[1].is_empty());
+        assert_eq!(faults[1].timestamp(), None);
+    }
+
+    #[test]
+    fn password_is_masked_in_debug_and_serialize_output() {
+        let password = Password::new("1234");
+        assert_eq!(password.expose(), "1234");
+        assert_eq!(format!("{:?}", password), format!("{:?}", MASK));
+        assert_eq!(serde_json::to_string(&password).unwrap(), format!("\"{}\"", MASK));
+    }
+}
@@ -0,0 +1,661 @@
+// izone/src/report.rs
+
+//! Serializable views of the controller models for machine-readable output.
+//!
+//! Field names are snake_case and stable across releases (see the "Output
+//! Formats" section of the README). Temperatures are in °C, percentages are
+//! 0-100 and enums are lowercase names rather than raw codes.
+
+use serde::Serialize;
+
+use crate::helpers::{
+    get_allowed_fan_speeds, get_cpu_type_text, get_fan_auto_type_text, get_fan_speed_text, get_gas_heat_type_text, get_oem_make_text, get_ras_text,
+    get_sensor_type_text, get_system_mode_text, get_temperzone_fan_type_text, get_temperzone_mode_type_text,
+    get_unit_brand_set_text, get_unit_brand_text, get_zone_mode_text, get_zone_type_text, CTRL_ZONE_UNIT_SETPOINT,
+};
+use crate::models::{
+    AcFault, Firmware, GasHeat, SchedulesV2, SystemV2, Temperzone, TemperzoneHistory8, TemperzoneInfoV2, ZonesV2,
+};
+
+/// Converts a x100 controller temperature to °C.
+pub fn celsius(raw: u32) -> f64 {
+    raw as f64 / 100.0
+}
+
+/// Lowercase, underscore-separated name for a display label ("Open/Close" -> "open_close").
+fn key_name(text: &str) -> String {
+    text.to_lowercase().replace(['/', ' '], "_")
+}
+
+/// Overall system status (request type 1).
+#[derive(Debug, Clone, Serialize)]
+pub struct SystemReport {
+    pub on: bool,
+    pub mode: String,
+    pub fan: String,
+    pub setpoint_c: f64,
+    pub temp_c: f64,
+    pub supply_c: f64,
+    pub ac_error: String,
+    pub sleep_timer_min: u32,
+    pub tag1: String,
+    pub tag2: String,
+    pub warnings: String,
+    pub zones: u8,
+    pub constants: u8,
+    pub eco_lock: bool,
+    pub eco_min_c: f64,
+    pub eco_max_c: f64,
+    /// ras, master or zones (the names `config return-air-sensor` takes).
+    pub control_source: String,
+    /// Zone index controlling the unit, or `None` when it uses the unit setpoint.
+    pub control_zone: Option<u8>,
+    /// `LockStatus`: the system is locked until the keycode is entered.
+    pub locked: bool,
+    /// `LockOn`: the lock timer is running.
+    pub lock_timer: bool,
+    /// `iSaveOn`, or `None` when the iSave option is not enabled (`iSaveEnable`).
+    pub isave: Option<bool>,
+}
+
+impl From<&SystemV2> for SystemReport {
+    fn from(sys: &SystemV2) -> Self {
+        SystemReport {
+            on: sys.sys_on,
+            mode: key_name(&get_system_mode_text(sys.sys_mode)),
+            fan: key_name(&get_fan_speed_text(sys.sys_fan)),
+            setpoint_c: celsius(sys.setpoint),
+            temp_c: celsius(sys.temp),
+            supply_c: celsius(sys.supply),
+            ac_error: sys.ac_error.trim().to_string(),
+            sleep_timer_min: sys.sleep_timer,
+            tag1: sys.tag1.clone(),
+            tag2: sys.tag2.clone(),
+            warnings: sys.warnings.clone(),
+            zones: sys.no_of_zones,
+            constants: sys.no_of_const,
+            eco_lock: sys.eco_lock != 0,
+            eco_min_c: celsius(sys.eco_min),
+            eco_max_c: celsius(sys.eco_max),
+            control_source: get_ras_text(sys.ras).to_lowercase(),
+            control_zone: (sys.ctrl_zone != CTRL_ZONE_UNIT_SETPOINT).then_some(sys.ctrl_zone),
+            locked: sys.lock_status == 1,
+            lock_timer: sys.lock_on == 1,
+            isave: (sys.isave_enable != 0).then_some(sys.isave_on == 1),
+        }
+    }
+}
+
+/// Status of a single zone (request type 2).
+#[derive(Debug, Clone, Serialize)]
+pub struct ZoneReport {
+    pub index: u8,
+    pub name: String,
+    pub mode: String,
+    pub temp_c: f64,
+    pub setpoint_c: f64,
+    pub damper_pct: u8,
+    pub zone_type: String,
+    /// ccts, csm, czco, crfs, cs or none (the names `set-type --sensor` takes).
+    pub sensor_type: String,
+    pub max_air_pct: u8,
+    pub min_air_pct: u8,
+    pub constant_no: u8,
+    pub constant_active: bool,
+    pub master: bool,
+    pub damper_fault: bool,
+    pub sensor_fault: bool,
+    pub damper_skip: bool,
+    pub calibration_c: f64,
+    pub rf_signal: u8,
+    pub battery: u8,
+    pub area_m2: u32,
+    pub bypass: bool,
+    pub balance_max_pct: u8,
+    pub balance_min_pct: u8,
+}
+
+impl From<&ZonesV2> for ZoneReport {
+    fn from(zone: &ZonesV2) -> Self {
+        ZoneReport {
+            index: zone.index,
+            name: zone.name.clone(),
+            mode: key_name(&get_zone_mode_text(zone.mode)),
+            temp_c: celsius(zone.temp),
+            setpoint_c: celsius(zone.setpoint),
+            damper_pct: zone.damper_pos,
+            zone_type: key_name(&get_zone_type_text(zone.zone_type)),
+            sensor_type: get_sensor_type_text(zone.sens_type)
+                .split(' ')
+                .next()
+                .unwrap_or_default()
+                .to_lowercase(),
+            max_air_pct: zone.max_air,
+            min_air_pct: zone.min_air,
+            constant_no: zone.constant,
+            constant_active: zone.constant_a != 0,
+            master: zone.master != 0,
+            damper_fault: zone.damper_fault != 0,
+            sensor_fault: zone.sensor_fault != 0,
+            damper_skip: zone.damper_skip != 0,
+            calibration_c: zone.calibration as f64 / 10.0,
+            rf_signal: zone.rf_signal,
+            battery: zone.batt_volt,
+            area_m2: zone.area,
+            bypass: zone.bypass != 0,
+            balance_max_pct: zone.balance_max,
+            balance_min_pct: zone.balance_min,
+        }
+    }
+}
+
+/// Zone settings stored in a favourite / schedule.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleZoneReport {
+    pub index: u8,
+    pub mode: String,
+    pub setpoint_c: f64,
+}
+
+/// A favourite / schedule (request type 3).
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleReport {
+    pub index: u8,
+    pub name: String,
+    pub enabled: bool,
+    pub mode: Option<String>,
+    pub fan: Option<String>,
+    /// "HH:MM", or `None` when the schedule has no start time.
+    pub start: Option<String>,
+    /// "HH:MM", or `None` when the schedule has no stop time.
+    pub stop: Option<String>,
+    /// Three-letter lowercase day names ("mon" ... "sun").
+    pub days: Vec<String>,
+    pub zones: Vec<ScheduleZoneReport>,
+}
+
+/// Formats a schedule time, `None` for the "not set" markers (255:255 and 31:63).
+fn schedule_time(hour: Option<u8>, minute: Option<u8>) -> Option<String> {
+    match (hour.unwrap_or(0), minute.unwrap_or(0)) {
+        (255, 255) | (31, 63) => None,
+        (h, m) => Some(format!("{:02}:{:02}", h, m)),
+    }
+}
+
+impl From<&SchedulesV2> for ScheduleReport {
+    fn from(schedule: &SchedulesV2) -> Self {
+        let d = &schedule.days_enabled;
+        let days = [
+            (d.monday, "mon"), (d.tuesday, "tue"), (d.wednesday, "wed"), (d.thursday, "thu"),
+            (d.friday, "fri"), (d.saturday, "sat"), (d.sunday, "sun"),
+        ]
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, day)| day.to_string())
+        .collect();
+
+        let zones = schedule
+            .zones
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(i, z)| ScheduleZoneReport {
+                index: i as u8,
+                mode: key_name(&get_zone_mode_text(z.mode)),
+                setpoint_c: celsius(z.setpoint),
+            })
+            .collect();
+
+        ScheduleReport {
+            index: schedule.index,
+            name: schedule.name.clone(),
+            enabled: schedule.active,
+            mode: schedule.mode.map(|m| key_name(&get_system_mode_text(m))),
+            fan: schedule.fan.map(|f| key_name(&get_fan_speed_text(f))),
+            start: schedule_time(schedule.start_h, schedule.start_m),
+            stop: schedule_time(schedule.stop_h, schedule.stop_m),
+            days,
+            zones,
+        }
+    }
+}
+
+/// An entry of the AC unit fault history (request type 4). The code is kept
+/// as reported: the API documents no fault codes, they are brand specific.
+#[derive(Debug, Clone, Serialize)]
+pub struct FaultReport {
+    /// 1-based position in the controller's history.
+    pub slot: u8,
+    pub code: String,
+    /// "YYYY-MM-DD HH:MM", or `None` if the controller sent an invalid date.
+    pub time: Option<String>,
+}
+
+impl FaultReport {
+    pub fn new(slot: u8, fault: &AcFault) -> Self {
+        FaultReport {
+            slot,
+            code: fault.code.trim().to_string(),
+            time: fault.timestamp(),
+        }
+    }
+}
+
+/// The UC8 sends 16-bit registers; negative temperatures may arrive as
+/// two's complement (e.g. 65486 for -5.0°C).
+fn uc8_signed(raw: i32) -> i32 {
+    if (32768..=65535).contains(&raw) { raw - 65536 } else { raw }
+}
+
+/// Converts a UC8 temperature or superheat (x10) to °C / K.
+///
+/// The vendor header gives these fields as plain `int` without a unit or
+/// scale. Tenths of a degree is an assumption taken from the UC8's own
+/// Modbus register map; if a unit reports implausible temperatures, this is
+/// the scaling to check.
+pub fn uc8_celsius(raw: i32) -> f64 {
+    uc8_signed(raw) as f64 / 10.0
+}
+
+/// Names of the set bits of a digital input/output bitmask, 1-based
+/// (`0b101` with prefix "do" -> `["do1", "do3"]`).
+pub fn active_bits(mask: u32, prefix: &str) -> Vec<String> {
+    (0..16).filter(|bit| mask & (1 << bit) != 0).map(|bit| format!("{}{}", prefix, bit + 1)).collect()
+}
+
+/// Temperzone unit settings from `SystemV2`, setpoints in °C.
+#[derive(Debug, Clone, Serialize)]
+pub struct TemperzoneConfigReport {
+    pub heat_setpoint_c: f64,
+    pub cool_setpoint_c: f64,
+    /// variable_speed or 3_speed
+    pub fan_type: String,
+    /// no_expansion, single_expansion, series_expansion or dry_mode
+    pub mode_type: String,
+    pub quiet: bool,
+}
+
+impl From<&Temperzone> for TemperzoneConfigReport {
+    fn from(tz: &Temperzone) -> Self {
+        TemperzoneConfigReport {
+            heat_setpoint_c: celsius(tz.heat_setpoint),
+            cool_setpoint_c: celsius(tz.cool_setpoint),
+            fan_type: key_name(&get_temperzone_fan_type_text(tz.fan_type)),
+            mode_type: key_name(&get_temperzone_mode_type_text(tz.mode_type)),
+            quiet: tz.quiet != 0,
+        }
+    }
+}
+
+/// Universal (gas heat) controller settings from `SystemV2`.
+#[derive(Debug, Clone, Serialize)]
+pub struct GasHeatReport {
+    /// GasHeatType_e as a lowercase name, e.g. 1_gas_heat
+    pub gas_type: String,
+    pub min_run_time_min: u8,
+    pub anticycle_time_min: u8,
+    pub stage_offset_c: f64,
+    pub stage_delay_min: u8,
+    /// true when the fan cycles with the unit, false when it runs continuously
+    pub cycle_fan_cool: bool,
+    pub cycle_fan_heat: bool,
+}
+
+impl From<&GasHeat> for GasHeatReport {
+    fn from(gas: &GasHeat) -> Self {
+        GasHeatReport {
+            gas_type: key_name(&get_gas_heat_type_text(gas.gas_type).replace(',', "")),
+            min_run_time_min: gas.min_run_time,
+            anticycle_time_min: gas.anticycle_time,
+            stage_offset_c: gas.stage_offset as f64 / 10.0,
+            stage_delay_min: gas.stage_delay,
+            cycle_fan_cool: gas.cycle_fan_cool != 0,
+            cycle_fan_heat: gas.cycle_fan_heat != 0,
+        }
+    }
+}
+
+/// Temperzone outdoor unit status (request type 5). Temperatures are in °C,
+/// superheat in K, pressures in kPa and expansion valve positions in steps.
+#[derive(Debug, Clone, Serialize)]
+pub struct TemperzoneStatusReport {
+    pub outdoor_coil_c: f64,
+    pub indoor_coil_c: f64,
+    pub ambient_c: f64,
+    pub suction_line_c: f64,
+    pub discharge_line_c: f64,
+    pub de_ice_sensor_c: f64,
+    pub evaporating_c: f64,
+    pub condensing_c: f64,
+    pub controller_c: f64,
+    pub suction_superheat_k: f64,
+    pub discharge_superheat_k: f64,
+    pub suction_pressure_kpa: i32,
+    pub discharge_pressure_kpa: i32,
+    pub outdoor_fan_speed: i32,
+    pub indoor_fan_speed: i32,
+    pub exv1_steps: i32,
+    pub exv2_steps: i32,
+    pub capacity_pct: i32,
+    pub unit_mode: i32,
+    pub indoor_coil1_c: f64,
+    pub indoor_suction_line1_c: f64,
+    pub indoor_coil2_c: f64,
+    pub indoor_suction_line2_c: f64,
+    pub supply_air_c: f64,
+    pub return_air_c: f64,
+    /// Active digital inputs ("di1" ... "di16").
+    pub digital_inputs: Vec<String>,
+    /// Active digital outputs ("do1" ... "do16").
+    pub digital_outputs: Vec<String>,
+    /// Active bits of the separate output status register ("out1" ... "out16").
+    pub output_status: Vec<String>,
+    pub uc8_id_code: u32,
+    pub uc8_software_version: u32,
+    pub min_on_off_s: u32,
+    pub min_off_on_s: u32,
+    pub min_on_on_s: u32,
+}
+
+impl From<&TemperzoneInfoV2> for TemperzoneStatusReport {
+    fn from(info: &TemperzoneInfoV2) -> Self {
+        let t = &info.temps;
+        let o = &info.outputs;
+        let th = &info.thermostats;
+        TemperzoneStatusReport {
+            outdoor_coil_c: uc8_celsius(t.outdoor_coil),
+            indoor_coil_c: uc8_celsius(t.indoor_coil),
+            ambient_c: uc8_celsius(t.ambient),
+            suction_line_c: uc8_celsius(t.suction_line),
+            discharge_line_c: uc8_celsius(t.discharge_line),
+            de_ice_sensor_c: uc8_celsius(t.de_ice_sensor),
+            evaporating_c: uc8_celsius(t.evaporating),
+            condensing_c: uc8_celsius(t.condensing),
+            controller_c: uc8_celsius(t.controller),
+            suction_superheat_k: uc8_celsius(t.suction_side_superheat),
+            discharge_superheat_k: uc8_celsius(t.discharge_side_superheat),
+            suction_pressure_kpa: t.suction_line_pressure,
+            discharge_pressure_kpa: t.discharge_line_pressure,
+            outdoor_fan_speed: o.outdoor_fan_speed,
+            indoor_fan_speed: o.indoor_fan_speed,
+            exv1_steps: o.exv1_position,
+            exv2_steps: o.exv2_position,
+            capacity_pct: o.unit_capacity,
+            unit_mode: o.unit_mode,
+            indoor_coil1_c: uc8_celsius(th.indoor_unit_coil_temperature1),
+            indoor_suction_line1_c: uc8_celsius(th.indoor_unit_suction_line_temperature1),
+            indoor_coil2_c: uc8_celsius(th.indoor_unit_coil_temperature2),
+            indoor_suction_line2_c: uc8_celsius(th.indoor_unit_suction_line_temperature2),
+            supply_air_c: uc8_celsius(th.supply_air_temperature),
+            return_air_c: uc8_celsius(th.return_air_temperature),
+            digital_inputs: active_bits(info.input_status.inputs, "di"),
+            digital_outputs: active_bits(o.digital_outputs, "do"),
+            output_status: active_bits(info.output_status.outputs, "out"),
+            uc8_id_code: info.uc8.id_code,
+            uc8_software_version: info.uc8.software_version,
+            min_on_off_s: info.timers.minimum_on_off_time,
+            min_off_on_s: info.timers.minimum_off_on_time,
+            min_on_on_s: info.timers.minimum_on_on_time,
+        }
+    }
+}
+
+/// Temperzone `History8` counters (request type 5); the reserved registers are left out.
+#[derive(Debug, Clone, Serialize)]
+pub struct TemperzoneHistoryReport {
+    pub modbus_address: u32,
+    pub running_hours: u32,
+    pub running_minutes: u32,
+    pub cooling_cycles: u32,
+    pub heating_cycles: u32,
+    pub deice_cycles: u32,
+    pub hp_trips: u32,
+    pub lp_trips: u32,
+    pub frost_protection_events: u32,
+    pub freeze_protection_events: u32,
+    pub high_temperature_protection_events: u32,
+    pub high_suction_line_temperature_protection_events: u32,
+    pub overload_protection_events: u32,
+    pub low_discharge_superheat_protection_events: u32,
+    pub high_discharge_superheat_protection_events: u32,
+    pub power_on_resets: u32,
+    pub indoor_coil_sensor_faults: u32,
+    pub outdoor_coil_sensor_faults: u32,
+    pub outdoor_ambient_sensor_faults: u32,
+    pub discharge_line_sensor_faults: u32,
+    pub suction_line_sensor_faults: u32,
+    pub deice_sensor_faults: u32,
+    pub high_pressure_sensor_faults: u32,
+    pub low_pressure_sensor_faults: u32,
+    pub high_board_temperature_faults: u32,
+    pub reverse_cycle_valve_faults: u32,
+    pub iuc_communication_faults: u32,
+    pub iuc_faults: u32,
+    pub inverter_faults: u32,
+    pub compressor_out_of_envelope_faults: u32,
+}
+
+impl From<&TemperzoneHistory8> for TemperzoneHistoryReport {
+    fn from(h: &TemperzoneHistory8) -> Self {
+        TemperzoneHistoryReport {
+            modbus_address: h.modbus_address,
+            running_hours: h.total_running_hours,
+            running_minutes: h.total_running_minutes,
+            cooling_cycles: h.total_cooling_cycles_made,
+            heating_cycles: h.total_heating_cycles_made,
+            deice_cycles: h.total_deice_cycles_made,
+            hp_trips: h.hp_trip_events,
+            lp_trips: h.lp_trip_events,
+            frost_protection_events: h.frost_protection_events,
+            freeze_protection_events: h.freeze_protection_events,
+            high_temperature_protection_events: h.high_temperature_protection_events,
+            high_suction_line_temperature_protection_events: h.high_suction_line_temperature_protection_events,
+            overload_protection_events: h.overload_protection_events,
+            low_discharge_superheat_protection_events: h.low_discharge_superheat_protection_events,
+            high_discharge_superheat_protection_events: h.high_discharge_superheat_protection_events,
+            power_on_resets: h.number_of_power_on_reset_events,
+            indoor_coil_sensor_faults: h.indoor_coil_temperature_sensor_faults,
+            outdoor_coil_sensor_faults: h.outdoor_coil_temperature_sensor_faults,
+            outdoor_ambient_sensor_faults: h.outdoor_ambient_temperature_faults,
+            discharge_line_sensor_faults: h.discharge_line_temperature_sensor_faults,
+            suction_line_sensor_faults: h.suction_line_temperature_sensor_faults,
+            deice_sensor_faults: h.deice_temperature_sensor_faults,
+            high_pressure_sensor_faults: h.high_pressure_sensor_faults,
+            low_pressure_sensor_faults: h.low_pressure_sensor_faults,
+            high_board_temperature_faults: h.high_board_temperature_faults,
+            reverse_cycle_valve_faults: h.reverse_cycle_valve_faults,
+            iuc_communication_faults: h.iuc_communication_faults,
+            iuc_faults: h.iuc_faults,
+            inverter_faults: h.inverter_faults,
+            compressor_out_of_envelope_faults: h.compressor_out_of_envelope_faults,
+        }
+    }
+}
+
+/// Which AC unit functions iZone controls (`iZone*`) and which iZone
+/// functions the unit's own controller controls (`Ext*`).
+#[derive(Debug, Clone, Serialize)]
+pub struct LinkageReport {
+    pub izone_on_off: bool,
+    pub izone_mode: bool,
+    pub izone_fan: bool,
+    pub izone_setpoint: bool,
+    pub unit_on_off: bool,
+    pub unit_mode: bool,
+    pub unit_fan: bool,
+    pub unit_setpoint: bool,
+}
+
+impl LinkageReport {
+    /// The flag for a `helpers::get_linkage_key` key.
+    pub fn get(&self, key: &str) -> Option<bool> {
+        match key {
+            "iZoneOnOff" => Some(self.izone_on_off),
+            "iZoneMode" => Some(self.izone_mode),
+            "iZoneFan" => Some(self.izone_fan),
+            "iZoneSetpoint" => Some(self.izone_setpoint),
+            "ExtOnOff" => Some(self.unit_on_off),
+            "ExtMode" => Some(self.unit_mode),
+            "ExtFan" => Some(self.unit_fan),
+            "ExtSetpoint" => Some(self.unit_setpoint),
+            _ => None,
+        }
+    }
+}
+
+impl From<&SystemV2> for LinkageReport {
+    fn from(sys: &SystemV2) -> Self {
+        LinkageReport {
+            izone_on_off: sys.izone_on_off != 0,
+            izone_mode: sys.izone_mode != 0,
+            izone_fan: sys.izone_fan != 0,
+            izone_setpoint: sys.izone_setpoint != 0,
+            unit_on_off: sys.ext_on_off != 0,
+            unit_mode: sys.ext_mode != 0,
+            unit_fan: sys.ext_fan != 0,
+            unit_setpoint: sys.ext_setpoint != 0,
+        }
+    }
+}
+
+/// Installer and display flags from `SystemV2`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PanelReport {
+    /// `AutoOff`: auto off function.
+    pub auto_off: bool,
+    /// `RoomTempDisp`: room temperature shown on the controller.
+    pub room_temp_display: bool,
+    /// `WiredLeds`: LEDs on the wired sensors.
+    pub wired_leds: bool,
+    /// `HideInduct`: induct temperature hidden.
+    pub hide_induct: bool,
+    /// `ShowActTemps`: zone list shows actual temperatures instead of airflow.
+    pub show_actual_temps: bool,
+    /// `OutOfViewRAS` (undocumented, shown only; there is no command to set it).
+    pub out_of_view_ras: bool,
+}
+
+impl PanelReport {
+    /// The flag for a `helpers::get_panel_key` key.
+    pub fn get(&self, key: &str) -> Option<bool> {
+        match key {
+            "AutoOff" => Some(self.auto_off),
+            "RoomTempDisp" => Some(self.room_temp_display),
+            "SetWiredLeds" => Some(self.wired_leds),
+            "HideInduct" => Some(self.hide_induct),
+            "ShowActTemps" => Some(self.show_actual_temps),
+            _ => None,
+        }
+    }
+}
+
+impl From<&SystemV2> for PanelReport {
+    fn from(sys: &SystemV2) -> Self {
+        PanelReport {
+            auto_off: sys.auto_off != 0,
+            room_temp_display: sys.room_temp_disp != 0,
+            wired_leds: sys.wired_leds != 0,
+            hide_induct: sys.hide_induct != 0,
+            show_actual_temps: sys.show_act_temps != 0,
+            out_of_view_ras: sys.out_of_view_ras != 0,
+        }
+    }
+}
+
+/// Fan auto settings from `SystemV2` and the fan speeds they allow.
+#[derive(Debug, Clone, Serialize)]
+pub struct FanAutoReport {
+    /// `None` when the firmware does not report `FanAutoEn`.
+    pub enabled: Option<bool>,
+    /// 2_speed, 3_speed, variable_speed or 4_speed
+    pub fan_type: String,
+    /// Fan airflow rating (`FanCapacity`).
+    pub fan_capacity: u16,
+    pub unit_capacity_kw: u16,
+    /// Speeds `izone fan` accepts, e.g. low, high, auto
+    pub allowed_speeds: Vec<String>,
+}
+
+impl From<&SystemV2> for FanAutoReport {
+    fn from(sys: &SystemV2) -> Self {
+        FanAutoReport {
+            enabled: sys.fan_auto_en.map(|enabled| enabled != 0),
+            fan_type: key_name(&get_fan_auto_type_text(sys.fan_auto_type)),
+            fan_capacity: sys.fan_capacity,
+            unit_capacity_kw: sys.fan_unit_capacity,
+            allowed_speeds: get_allowed_fan_speeds(sys.fan_auto_en, sys.fan_auto_type)
+                .iter()
+                .map(|&speed| key_name(&get_fan_speed_text(speed)))
+                .collect(),
+        }
+    }
+}
+
+/// Hardware inventory: the `SystemV2` hardware fields plus the firmware list
+/// (request type 6).
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceReport {
+    /// Controller board (`CpuType`): C220, C150 or Coolbreeze.
+    pub cpu_type: String,
+    /// System make (`OemMake`).
+    pub oem_make: String,
+    /// AC unit detected by the controller (`AcUnitBrand`).
+    pub ac_unit_brand: String,
+    /// AC unit chosen during installation (`AcUnitBrandSet`).
+    pub ac_unit_brand_set: String,
+    /// `SysType`: 310 supports unit control, 210 does not.
+    pub sys_type: u16,
+    pub sys_no: u8,
+    pub firmware: Vec<Firmware>,
+}
+
+impl DeviceReport {
+    pub fn new(sys: &SystemV2, firmware: Vec<Firmware>) -> Self {
+        DeviceReport {
+            cpu_type: get_cpu_type_text(sys.cpu_type),
+            oem_make: get_oem_make_text(sys.oem_make),
+            ac_unit_brand: get_unit_brand_text(sys.ac_unit_brand),
+            ac_unit_brand_set: get_unit_brand_set_text(sys.ac_unit_brand_set),
+            sys_type: sys.sys_type,
+            sys_no: sys.sys_no,
+            firmware,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uc8_readings_in_tenths() {
+        assert_eq!(uc8_celsius(352), 35.2);
+        assert_eq!(uc8_celsius(0), 0.0);
+        assert_eq!(uc8_celsius(32767), 3276.7);
+    }
+
+    #[test]
+    fn uc8_negative_readings_from_twos_complement() {
+        assert_eq!(uc8_signed(65486), -50);
+        assert_eq!(uc8_celsius(65486), -5.0);
+        assert_eq!(uc8_celsius(65535), -0.1);
+        assert_eq!(uc8_signed(32768), -32768);
+        // Values the bridge already sent signed, or that are not 16-bit, are kept
+        assert_eq!(uc8_celsius(-50), -5.0);
+        assert_eq!(uc8_signed(65536), 65536);
+    }
+
+    #[test]
+    fn active_bits_names_set_bits_from_one() {
+        assert!(active_bits(0, "do").is_empty());
+        assert_eq!(active_bits(0b101, "do"), ["do1", "do3"]);
+        assert_eq!(active_bits(0x8001, "di"), ["di1", "di16"]);
+        assert_eq!(active_bits(0xFFFF, "out").len(), 16);
+    }
+
+    #[test]
+    fn active_bits_ignores_bits_above_the_sixteen_channels() {
+        assert!(active_bits(0x1_0000, "do").is_empty());
+        assert_eq!(active_bits(0xFFFF_0002, "do"), ["do2"]);
+    }
+}
@@ -0,0 +1,285 @@
+// izone/src/zone_map.rs
+
+//! Zone name to index mapping, from the `[zones]` config table or discovered
+//! from the controller.
+
+use std::collections::BTreeMap;
+
+use crate::client::IzoneClient;
+use crate::constants::MAX_ZONES;
+use crate::error::{IzoneError, Result};
+use crate::models::ZonesV2;
+
+/// Zone names and their 0-based API indices.
+#[derive(Debug, Clone, Default)]
+pub struct ZoneMap {
+    /// (normalised name, index), sorted by index.
+    entries: Vec<(String, u8)>,
+    /// Number of valid indices for numeric lookups.
+    zone_count: u8,
+    /// The zones read by [`ZoneMap::discover`], kept so read-only commands
+    /// do not query them again. Empty when the map comes from the config.
+    zones: Vec<ZonesV2>,
+}
+
+/// Lowercase with spaces and dashes as underscores, so "Master Bed",
+/// "master_bed" and "MASTER-BED" all match.
+fn normalise(name: &str) -> String {
+    name.trim().to_lowercase().replace([' ', '-'], "_")
+}
+
+/// Edit distance, used to accept small typos ("kitchn").
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
+impl ZoneMap {
+    /// Builds the map from the `[zones]` config table (name = index). The
+    /// controller is not asked for `NoOfZones`, so numeric lookups are
+    /// bounded by the highest configured index instead.
+    pub fn from_config(zones: &BTreeMap<String, u8>) -> Result<Self> {
+        let mut entries = Vec::new();
+        for (name, &index) in zones {
+            if index >= MAX_ZONES {
+                return Err(IzoneError::validation(format!(
+                    "Zone '{}' in the [zones] config table has index {}; valid indices are 0-{}.",
+                    name, index, MAX_ZONES - 1
+                )));
+            }
+            entries.push((normalise(name), index));
+        }
+        entries.sort_by_key(|(_, index)| *index);
+        let zone_count = entries.last().map_or(0, |(_, index)| index + 1);
+        Ok(ZoneMap { entries, zone_count, zones: Vec::new() })
+    }
+
+    /// Reads `NoOfZones` from the system status, then each zone's `Name`.
+    /// The zones are kept (see [`ZoneMap::zone`]), so the N+1 queries are
+    /// made once per invocation rather than again by each read.
+    pub fn discover(client: &IzoneClient) -> Result<Self> {
+        let zone_count = client.system()?.no_of_zones.min(MAX_ZONES);
+        let mut entries = Vec::new();
+        let mut zones = Vec::new();
+        for index in 0..zone_count {
+            let mut zone = client.zone(index)?;
+            zone.index = index;
+            entries.push((normalise(&zone.name), index));
+            zones.push(zone);
+        }
+        log::debug!("Discovered {} zones: {:?}", zone_count, entries);
+        Ok(ZoneMap { entries, zone_count, zones })
+    }
+
+    /// Uses the config table when there is one, otherwise discovers the zones.
+    pub fn load(zones: Option<&BTreeMap<String, u8>>, client: &IzoneClient) -> Result<Self> {
+        match zones {
+            Some(zones) if !zones.is_empty() => Self::from_config(zones),
+            _ => Self::discover(client),
+        }
+    }
+
+    /// (name, index) pairs, sorted by index.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, u8)> {
+        self.entries.iter().map(|(name, index)| (name.as_str(), *index))
+    }
+
+    /// Every index a zone command may address, named or not.
+    pub fn indices(&self) -> Vec<u8> {
+        if self.entries.is_empty() {
+            return (0..self.zone_count).collect();
+        }
+        let mut indices: Vec<u8> = self.entries.iter().map(|(_, index)| *index).collect();
+        indices.dedup();
+        indices
+    }
+
+    /// The zone as read by [`ZoneMap::discover`], or `None` when the map
+    /// comes from the config table. Not refreshed after a command is sent.
+    pub fn zone(&self, index: u8) -> Option<&ZonesV2> {
+        self.zones.get(usize::from(index))
+    }
+
+    /// The configured or discovered name for an index.
+    pub fn name_of(&self, index: u8) -> Option<&str> {
+        self.entries.iter().find(|(_, i)| *i == index).map(|(name, _)| name.as_str())
+    }
+
+    /// Resolves a zone argument to its index. Accepts a numeric index, or a
+    /// name matched case-insensitively: exact, then unique prefix, then
+    /// unique substring, then the closest name within two typos. Meant for
+    /// read-only commands; commands that change a zone use [`Self::resolve_strict`].
+    pub fn resolve(&self, query: &str) -> Result<u8> {
+        self.lookup(query, true)
+    }
+
+    /// Like [`Self::resolve`], but only accepts an index, an exact name or a
+    /// unique prefix, so a typo cannot change the wrong zone. A substring or
+    /// typo match is reported as a suggestion instead.
+    pub fn resolve_strict(&self, query: &str) -> Result<u8> {
+        self.lookup(query, false)
+    }
+
+    fn lookup(&self, query: &str, fuzzy: bool) -> Result<u8> {
+        if let Ok(index) = query.trim().parse::<u8>() {
+            if index < self.zone_count {
+                return Ok(index);
+            }
+            return Err(IzoneError::validation(format!(
+                "Zone index {} is out of range (0-{}).",
+                index,
+                self.zone_count.saturating_sub(1)
+            )));
+        }
+
+        let wanted = normalise(query);
+        if let Some((_, index)) = self.entries.iter().find(|(name, _)| *name == wanted) {
+            return Ok(*index);
+        }
+
+        let prefix: Vec<&(String, u8)> = self.entries.iter().filter(|(name, _)| name.starts_with(&wanted)).collect();
+        let substring: Vec<&(String, u8)> = self.entries.iter().filter(|(name, _)| name.contains(&wanted)).collect();
+        let (candidates, guessed) = if !prefix.is_empty() { (prefix, false) } else { (substring, true) };
+        match candidates.as_slice() {
+            [(_, index)] if fuzzy || !guessed => return Ok(*index),
+            [(name, _)] => return Err(self.not_exact(query, name)),
+            [] => {}
+            many => {
+                let names: Vec<&str> = many.iter().map(|(name, _)| name.as_str()).collect();
+                return Err(IzoneError::validation(format!(
+                    "Zone '{}' is ambiguous, it matches: {}",
+                    query,
+                    names.join(", ")
+                )));
+            }
+        }
+
+        let mut close: Vec<(usize, &(String, u8))> = self
+            .entries
+            .iter()
+            .map(|entry| (levenshtein(&wanted, &entry.0), entry))
+            .filter(|(distance, _)| *distance <= 2)
+            .collect();
+        close.sort_by_key(|(distance, _)| *distance);
+        let closest = match close.as_slice() {
+            [(_, entry)] => Some(entry),
+            [(d1, entry), (d2, _), ..] if d1 < d2 => Some(entry),
+            _ => None,
+        };
+        match closest {
+            Some((_, index)) if fuzzy => Ok(*index),
+            Some((name, _)) => Err(self.not_exact(query, name)),
+            None => Err(IzoneError::validation(format!(
+                "Unknown zone '{}'.\nAvailable zones: {}",
+                query,
+                self.available()
+            ))),
+        }
+    }
+
+    fn not_exact(&self, query: &str, name: &str) -> IzoneError {
+        IzoneError::validation(format!(
+            "Zone '{}' is not an exact name or prefix; did you mean '{}'?\nCommands that change a zone need the name, a unique prefix or the index.",
+            query, name
+        ))
+    }
+
+    fn available(&self) -> String {
+        if self.entries.is_empty() {
+            return format!("none named, use an index 0-{}", self.zone_count.saturating_sub(1));
+        }
+        let names: Vec<String> = self.entries.iter().map(|(name, index)| format!("{} ({})", name, index)).collect();
+        names.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_map() -> ZoneMap {
+        let zones = [("Kitchen", 0), ("Master Bed", 1), ("bed1", 2), ("bed2", 3), ("Living", 4), ("Theatre", 5)];
+        let table: BTreeMap<String, u8> = zones.iter().map(|(name, index)| (name.to_string(), *index)).collect();
+        ZoneMap::from_config(&table).unwrap()
+    }
+
+    fn message(result: Result<u8>) -> String {
+        match result {
+            Err(IzoneError::Validation(message)) => message,
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn exact_names_and_unique_prefixes_resolve_for_both_lookups() {
+        let map = config_map();
+        for resolve in [ZoneMap::resolve, ZoneMap::resolve_strict] {
+            assert_eq!(resolve(&map, "KITCHEN").unwrap(), 0);
+            assert_eq!(resolve(&map, "master-bed").unwrap(), 1);
+            assert_eq!(resolve(&map, "kit").unwrap(), 0);
+            assert_eq!(resolve(&map, "3").unwrap(), 3);
+        }
+    }
+
+    #[test]
+    fn ambiguous_prefixes_are_refused() {
+        let map = config_map();
+        let error = message(map.resolve("bed"));
+        assert!(error.contains("ambiguous"), "{}", error);
+        assert!(error.contains("bed1") && error.contains("bed2"), "{}", error);
+        assert!(map.resolve_strict("bed").is_err());
+    }
+
+    #[test]
+    fn resolve_strict_refuses_a_unique_substring() {
+        let map = config_map();
+        assert_eq!(map.resolve("ster").unwrap(), 1);
+        let error = message(map.resolve_strict("ster"));
+        assert!(error.contains("did you mean 'master_bed'"), "{}", error);
+    }
+
+    #[test]
+    fn typos_resolve_only_when_one_zone_is_closest() {
+        let map = config_map();
+        assert_eq!(map.resolve("kitchn").unwrap(), 0);
+        assert!(message(map.resolve_strict("kitchn")).contains("did you mean 'kitchen'"));
+        // "bed3" is one edit from both bed1 and bed2
+        assert!(message(map.resolve("bed3")).starts_with("Unknown zone 'bed3'"));
+        assert!(message(map.resolve("xyz")).starts_with("Unknown zone 'xyz'"));
+    }
+
+    #[test]
+    fn indices_outside_the_zones_are_refused() {
+        let map = config_map();
+        assert_eq!(map.resolve("5").unwrap(), 5);
+        assert_eq!(message(map.resolve("6")), "Zone index 6 is out of range (0-5).");
+        assert!(map.resolve_strict("12").is_err());
+
+        let discovered = ZoneMap { entries: Vec::new(), zone_count: 8, zones: Vec::new() };
+        assert_eq!(discovered.resolve("7").unwrap(), 7);
+        assert!(discovered.resolve("8").is_err());
+    }
+
+    #[test]
+    fn config_indices_beyond_the_api_limit_are_refused() {
+        let table: BTreeMap<String, u8> = [("attic".to_string(), MAX_ZONES)].into_iter().collect();
+        assert!(ZoneMap::from_config(&table).is_err());
+    }
+
+    #[test]
+    fn levenshtein_counts_edits() {
+        assert_eq!(levenshtein("kitchen", "kitchen"), 0);
+        assert_eq!(levenshtein("kitchn", "kitchen"), 1);
+        assert_eq!(levenshtein("ktichen", "kitchen"), 2);
+        assert_eq!(levenshtein("", "bed"), 3);
+    }
+}